@@ -0,0 +1,60 @@
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/*
+When the `embedded-assets` feature is enabled, generate a table of every file in `assets/` for
+`embedded_assets` to `include!`.
+*/
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    if env::var_os("CARGO_FEATURE_EMBEDDED_ASSETS").is_none() {
+        return;
+    }
+
+    let asset_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("assets");
+    println!("cargo:rerun-if-changed={}", asset_dir.display());
+
+    let mut files = Vec::new();
+    collect_files(&asset_dir, &mut files);
+    files.sort();
+
+    let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("embedded_assets.rs");
+    let mut out = fs::File::create(out_path).unwrap();
+
+    writeln!(out, "&[").unwrap();
+    for file in files {
+        println!("cargo:rerun-if-changed={}", file.display());
+
+        let relative_path = file
+            .strip_prefix(&asset_dir)
+            .unwrap()
+            .components()
+            .map(|component| component.as_os_str().to_str().unwrap())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        writeln!(
+            out,
+            "    ({:?}, include_bytes!({:?})),",
+            relative_path,
+            file.display().to_string()
+        )
+        .unwrap();
+    }
+    writeln!(out, "]").unwrap();
+}
@@ -0,0 +1,355 @@
+use std::{collections::BTreeMap, fmt};
+
+use bevy::{
+    input::InputSystem,
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, TypeInfo, Typed, VariantInfo},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::settings::Settings;
+
+/**
+Something the player can do by pressing a button, independent of which button it's bound to.
+
+Systems read these from `Res<Input<InputAction>>` instead of reading the keyboard and mouse.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveForward,
+    MoveBackward,
+    MoveLeft,
+    MoveRight,
+    Hover,
+    Reset,
+    /// Turn the view by moving the mouse while this is held.
+    Rotate,
+    Pause,
+    /// Add an item to the level in the level editor.
+    SpawnItem,
+    /// Remove the selected items from the level in the level editor.
+    DeleteItem,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 10] = [
+        InputAction::MoveForward,
+        InputAction::MoveBackward,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Hover,
+        InputAction::Reset,
+        InputAction::Rotate,
+        InputAction::Pause,
+        InputAction::SpawnItem,
+        InputAction::DeleteItem,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::MoveForward => "forward",
+            InputAction::MoveBackward => "backward",
+            InputAction::MoveLeft => "left",
+            InputAction::MoveRight => "right",
+            InputAction::Hover => "hover",
+            InputAction::Reset => "reset",
+            InputAction::Rotate => "look around",
+            InputAction::Pause => "pause",
+            InputAction::SpawnItem => "editor: add",
+            InputAction::DeleteItem => "editor: delete",
+        }
+    }
+
+    fn default_bindings(self) -> Vec<Binding> {
+        let binding = match self {
+            InputAction::MoveForward => Binding::Key(KeyCode::W),
+            InputAction::MoveBackward => Binding::Key(KeyCode::S),
+            InputAction::MoveLeft => Binding::Key(KeyCode::A),
+            InputAction::MoveRight => Binding::Key(KeyCode::D),
+            InputAction::Hover => Binding::Key(KeyCode::Space),
+            InputAction::Reset => Binding::Key(KeyCode::R),
+            InputAction::Rotate => Binding::Mouse(MouseButton::Right),
+            InputAction::Pause => Binding::Key(KeyCode::Escape),
+            InputAction::SpawnItem => Binding::Key(KeyCode::Space),
+            InputAction::DeleteItem => Binding::Key(KeyCode::Delete),
+        };
+
+        vec![binding]
+    }
+}
+
+/**
+A key or mouse button.
+
+It's saved as the name of the key, such as `"W"` or `"Space"`, or the name of the mouse button
+prefixed with `Mouse`, such as `"MouseRight"`.
+*/
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+/// The unit variant of an enum called `variant_name`.
+fn unit_variant<T: FromReflect + Typed>(variant_name: &str) -> Option<T> {
+    /* Converting a variant that the enum doesn't have panics, rather than failing. */
+    match T::type_info() {
+        TypeInfo::Enum(info)
+            if matches!(info.variant(variant_name), Some(VariantInfo::Unit(_))) => {}
+        _ => return None,
+    }
+
+    T::from_reflect(&DynamicEnum::new(
+        std::any::type_name::<T>(),
+        variant_name,
+        DynamicVariant::Unit,
+    ))
+}
+
+impl TryFrom<String> for Binding {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        let binding = match name.strip_prefix("Mouse") {
+            Some(button_name) => unit_variant(button_name).map(Binding::Mouse),
+            None => unit_variant(&name).map(Binding::Key),
+        };
+
+        binding.ok_or_else(|| format!("unknown key or mouse button {:?}", name))
+    }
+}
+
+impl From<Binding> for String {
+    fn from(binding: Binding) -> Self {
+        binding.to_string()
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Binding::Key(key_code) => write!(f, "{:?}", key_code),
+            Binding::Mouse(mouse_button) => write!(f, "Mouse{:?}", mouse_button),
+        }
+    }
+}
+
+impl Binding {
+    fn pressed(self, keys: &Input<KeyCode>, mouse_buttons: &Input<MouseButton>) -> bool {
+        match self {
+            Binding::Key(key_code) => keys.pressed(key_code),
+            Binding::Mouse(mouse_button) => mouse_buttons.pressed(mouse_button),
+        }
+    }
+
+    fn just_pressed(self, keys: &Input<KeyCode>, mouse_buttons: &Input<MouseButton>) -> bool {
+        match self {
+            Binding::Key(key_code) => keys.just_pressed(key_code),
+            Binding::Mouse(mouse_button) => mouse_buttons.just_pressed(mouse_button),
+        }
+    }
+}
+
+/// The buttons bound to each action. Actions that haven't been rebound use their defaults.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct Bindings(BTreeMap<InputAction, Vec<Binding>>);
+
+impl Bindings {
+    pub fn get(&self, action: InputAction) -> Vec<Binding> {
+        self.0
+            .get(&action)
+            .cloned()
+            .unwrap_or_else(|| action.default_bindings())
+    }
+
+    pub fn set(&mut self, action: InputAction, bindings: Vec<Binding>) {
+        if bindings == action.default_bindings() {
+            self.0.remove(&action);
+        } else {
+            self.0.insert(action, bindings);
+        }
+    }
+
+    /// The bindings of an action, for display.
+    pub fn describe(&self, action: InputAction) -> String {
+        self.get(action)
+            .iter()
+            .map(Binding::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// While this resource exists, the next button that's pressed is bound to `action`.
+#[derive(Resource)]
+pub struct Rebinding {
+    pub action: InputAction,
+}
+
+/*
+An action stays pressed while any of its buttons are held, so releasing one of two held buttons
+doesn't release the action. Actions are only pressed by a button being pressed, not by one that's
+still held from before, so the button that was just bound doesn't trigger its new action.
+*/
+fn update_actions(
+    settings: Res<Settings>,
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    rebinding: Option<Res<Rebinding>>,
+    mut actions: ResMut<Input<InputAction>>,
+) {
+    actions.clear();
+
+    if rebinding.is_some() {
+        actions.release_all();
+        return;
+    }
+
+    for action in InputAction::ALL {
+        let bindings = settings.bindings.get(action);
+
+        if bindings
+            .iter()
+            .any(|binding| binding.just_pressed(&keys, &mouse_buttons))
+        {
+            actions.press(action);
+        } else if !bindings
+            .iter()
+            .any(|binding| binding.pressed(&keys, &mouse_buttons))
+        {
+            actions.release(action);
+        }
+    }
+}
+
+fn capture_rebinding(
+    mut commands: Commands,
+    rebinding: Res<Rebinding>,
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut settings: ResMut<Settings>,
+) {
+    let binding = keys
+        .get_just_pressed()
+        .next()
+        .map(|key_code| Binding::Key(*key_code))
+        .or_else(|| {
+            mouse_buttons
+                .get_just_pressed()
+                .find(|mouse_button| !matches!(mouse_button, MouseButton::Other(_)))
+                .map(|mouse_button| Binding::Mouse(*mouse_button))
+        });
+
+    if let Some(binding) = binding {
+        commands.remove_resource::<Rebinding>();
+
+        /* Escape cancels, unless it's pause that's being rebound. */
+        if binding == Binding::Key(KeyCode::Escape) && rebinding.action != InputAction::Pause {
+            return;
+        }
+
+        debug!("binding {:?} to {}", rebinding.action, binding);
+        settings.bindings.set(rebinding.action, vec![binding]);
+    }
+}
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Input<InputAction>>()
+            .add_system(
+                update_actions
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem),
+            )
+            .add_system(
+                capture_rebinding
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem)
+                    .run_if(resource_exists::<Rebinding>()),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bindings_round_trip_through_their_names() {
+        for binding in [
+            Binding::Key(KeyCode::W),
+            Binding::Key(KeyCode::Space),
+            Binding::Key(KeyCode::Key1),
+            Binding::Key(KeyCode::Escape),
+            Binding::Mouse(MouseButton::Left),
+            Binding::Mouse(MouseButton::Right),
+        ] {
+            let name = String::from(binding);
+            assert_eq!(Binding::try_from(name), Ok(binding));
+        }
+    }
+
+    #[test]
+    fn bindings_are_named_after_their_variants() {
+        assert_eq!(Binding::Key(KeyCode::Space).to_string(), "Space");
+        assert_eq!(Binding::Mouse(MouseButton::Right).to_string(), "MouseRight");
+    }
+
+    #[test]
+    fn unknown_names_are_rejected() {
+        assert!(Binding::try_from("Nonsense".to_string()).is_err());
+        assert!(Binding::try_from("MouseNonsense".to_string()).is_err());
+        assert!(Binding::try_from("Mouse".to_string()).is_err());
+        assert!(Binding::try_from(String::new()).is_err());
+    }
+
+    #[test]
+    fn only_rebound_actions_are_saved() {
+        let mut bindings = Bindings::default();
+        bindings.set(InputAction::Hover, vec![Binding::Mouse(MouseButton::Left)]);
+        bindings.set(InputAction::Reset, vec![Binding::Key(KeyCode::R)]);
+
+        assert_eq!(
+            serde_json::to_string(&bindings).unwrap(),
+            r#"{"Hover":["MouseLeft"]}"#
+        );
+        assert_eq!(
+            bindings.get(InputAction::Hover),
+            vec![Binding::Mouse(MouseButton::Left)]
+        );
+        assert_eq!(
+            bindings.get(InputAction::Reset),
+            vec![Binding::Key(KeyCode::R)]
+        );
+    }
+
+    #[test]
+    fn rebinding_back_to_the_default_forgets_the_binding() {
+        let mut bindings = Bindings::default();
+        bindings.set(InputAction::Hover, vec![Binding::Key(KeyCode::J)]);
+        bindings.set(InputAction::Hover, vec![Binding::Key(KeyCode::Space)]);
+
+        assert_eq!(bindings, Bindings::default());
+    }
+
+    #[test]
+    fn saved_bindings_are_read_back() {
+        let bindings: Bindings =
+            serde_json::from_str(r#"{"MoveForward":["Up","MouseMiddle"]}"#).unwrap();
+
+        assert_eq!(
+            bindings.get(InputAction::MoveForward),
+            vec![
+                Binding::Key(KeyCode::Up),
+                Binding::Mouse(MouseButton::Middle)
+            ]
+        );
+        assert_eq!(
+            bindings.describe(InputAction::MoveForward),
+            "Up, MouseMiddle"
+        );
+    }
+}
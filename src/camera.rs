@@ -1,11 +1,11 @@
-use std::f32::consts::PI;
-
 use bevy::{
     input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
 use bevy_atmosphere::prelude::AtmosphereCamera;
 
+use crate::settings::Settings;
+
 #[derive(Component)]
 pub struct Camera;
 
@@ -24,12 +24,8 @@ pub struct CameraBundle {
 impl CameraBundle {
     pub fn new(transform: Transform) -> Self {
         Self {
+            /* The field of view is set from `Settings`. */
             camera3d_bundle: Camera3dBundle {
-                projection: PerspectiveProjection {
-                    fov: (60.0 / 360.0) * 2.0 * PI,
-                    ..default()
-                }
-                .into(),
                 transform: transform.looking_at(Vec3::Y, Vec3::Y),
                 ..default()
             },
@@ -41,6 +37,7 @@ impl CameraBundle {
 }
 
 fn scroll_zoom(
+    settings: Res<Settings>,
     mut scroll_events: EventReader<MouseWheel>,
     mut query: Query<&mut Transform, With<Zoom>>,
 ) {
@@ -59,7 +56,7 @@ fn scroll_zoom(
         for mut transform in query.iter_mut() {
             debug!("zooming camera");
             let translation = transform.translation;
-            transform.translation += 0.08 * scroll_amount * -translation;
+            transform.translation += settings.zoom_step * scroll_amount * -translation;
         }
     }
 }
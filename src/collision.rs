@@ -3,9 +3,11 @@ use bevy_rapier3d::prelude::*;
 
 use crate::{
     controls::Controlled,
+    crumble::{CrumbleEvent, Crumbling},
     pause::PauseEvent,
-    reset::ResetEvent,
-    ui::{self, UI},
+    reset::ResetCause,
+    respawn::DeathEvent,
+    timer::LevelTimer,
     wall::{Wall, WallType},
 };
 
@@ -14,19 +16,27 @@ enum PlayerHit {
     Goal,
 }
 
-fn check_player_hit(
+fn player_and_target(
     player_query: &Query<&Controlled>,
     entity1: &Entity,
     entity2: &Entity,
-    wall_query: &Query<&Wall>,
-) -> Option<PlayerHit> {
-    let (player, target) = if player_query.contains(*entity1) {
+) -> Option<(Entity, Entity)> {
+    if player_query.contains(*entity1) {
         Some((*entity1, *entity2))
     } else if player_query.contains(*entity2) {
         Some((*entity2, *entity1))
     } else {
         None
-    }?;
+    }
+}
+
+fn check_player_hit(
+    player_query: &Query<&Controlled>,
+    entity1: &Entity,
+    entity2: &Entity,
+    wall_query: &Query<&Wall>,
+) -> Option<PlayerHit> {
+    let (player, target) = player_and_target(player_query, entity1, entity2)?;
 
     if let Ok(wall) = wall_query.get(target) {
         match wall.wall_type {
@@ -45,25 +55,48 @@ fn check_player_hit(
     }
 }
 
+/// Sent when the player completes the current level, once the timer has stopped.
+pub struct LevelCompleteEvent;
+
+pub fn complete_level(
+    timer: &mut LevelTimer,
+    pause_event: &mut EventWriter<PauseEvent>,
+    level_complete_event: &mut EventWriter<LevelCompleteEvent>,
+) {
+    if !timer.stopped {
+        timer.stop();
+        pause_event.send(PauseEvent::Pause);
+        level_complete_event.send(LevelCompleteEvent);
+    }
+}
+
 pub fn handle_player_collisions(
     mut collision_events: EventReader<CollisionEvent>,
-    check: (Query<&Controlled>, Query<&Wall>),
-    mut goal: (Res<AssetServer>, Commands, ResMut<UI>),
+    check: (Query<&Controlled>, Query<&Wall>, Query<(), With<Crumbling>>),
+    mut goal: (ResMut<LevelTimer>, EventWriter<LevelCompleteEvent>),
     mut pause_event: EventWriter<PauseEvent>,
-    mut reset_event: EventWriter<ResetEvent>,
+    mut death_event: EventWriter<DeathEvent>,
+    mut crumble_event: EventWriter<CrumbleEvent>,
 ) {
     for event in collision_events.iter() {
         if let CollisionEvent::Started(entity1, entity2, _) = event {
+            if let Some((_, target)) = player_and_target(&check.0, entity1, entity2) {
+                if check.2.contains(target) {
+                    crumble_event.send(CrumbleEvent { entity: target });
+                }
+            }
+
             let event = check_player_hit(&check.0, entity1, entity2, &check.1);
 
             if let Some(event) = event {
                 match event {
                     PlayerHit::Avoid => {
-                        reset_event.send(ResetEvent);
+                        death_event.send(DeathEvent {
+                            cause: ResetCause::Avoid,
+                        });
                     }
                     PlayerHit::Goal => {
-                        pause_event.send(PauseEvent::Pause);
-                        ui::overlay::level_complete::display(&goal.0, &mut goal.1, &mut goal.2);
+                        complete_level(&mut goal.0, &mut pause_event, &mut goal.1);
                     }
                 }
             }
@@ -2,5 +2,14 @@ use bevy::prelude::Resource;
 
 #[derive(Resource)]
 pub struct Config {
+    /**
+    The directory assets are read from.
+
+    With the `embedded-assets` feature, assets are compiled into the binary and files in this
+    directory only override them.
+    */
     pub asset_dir: String,
+
+    /// The campaigns to load, relative to `asset_dir`.
+    pub level_orders: Vec<String>,
 }
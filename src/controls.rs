@@ -1,18 +1,23 @@
-use bevy::{
-    input::{mouse::MouseButtonInput, ButtonState},
-    prelude::*,
-    window::PrimaryWindow,
+use bevy::{input::mouse::MouseMotion, prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::InputAction,
+    hover::HoverEvent,
+    reset::{ResetCause, ResetEvent},
+    settings::Settings,
 };
 
-use crate::{hover::HoverEvent, reset::ResetEvent};
-
-#[derive(Clone, Copy, Component)]
+#[derive(Clone, Copy, Component, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Controlled {
     pub rotating: bool,
     pub forward: bool,
     pub backward: bool,
     pub left: bool,
     pub right: bool,
+    /// How far the next simulation step will turn the player and tilt the camera, in radians.
+    #[serde(default)]
+    pub look: Vec2,
 }
 
 impl Controlled {
@@ -23,6 +28,7 @@ impl Controlled {
             backward: false,
             left: false,
             right: false,
+            look: Vec2::ZERO,
         }
     }
 
@@ -33,6 +39,7 @@ impl Controlled {
             backward,
             left,
             right,
+            look,
         } = self;
 
         *rotating = false;
@@ -40,6 +47,7 @@ impl Controlled {
         *backward = false;
         *left = false;
         *right = false;
+        *look = Vec2::ZERO;
     }
 }
 
@@ -54,73 +62,90 @@ pub struct Forward {
     pub value: Vec3,
 }
 
+/// How the player's velocity along the ground responds to movement input.
 #[derive(Component)]
 pub struct Speed {
-    pub value: f32,
+    /// The fastest the player can move by themselves, in metres per second.
+    pub max: f32,
+    /// How quickly the player speeds up while moving, in metres per second squared.
+    pub acceleration: f32,
+    /// How quickly the player slows down when they stop moving, in metres per second squared.
+    pub deceleration: f32,
 }
 
-fn handle_movement(keys: Res<Input<KeyCode>>, mut query: Query<&mut Controlled>) {
-    for mut controlled in query.iter_mut() {
-        if keys.just_pressed(KeyCode::W) {
-            controlled.forward = true;
-        }
-
-        if keys.just_released(KeyCode::W) {
-            controlled.forward = false;
-        }
-
-        if keys.just_pressed(KeyCode::A) {
-            controlled.left = true;
-        }
-
-        if keys.just_released(KeyCode::A) {
-            controlled.left = false;
-        }
-
-        if keys.just_pressed(KeyCode::S) {
-            controlled.backward = true;
-        }
-
-        if keys.just_released(KeyCode::S) {
-            controlled.backward = false;
+impl Default for Speed {
+    fn default() -> Self {
+        Self {
+            max: 3.5,
+            acceleration: 40.0,
+            deceleration: 40.0,
         }
+    }
+}
 
-        if keys.just_pressed(KeyCode::D) {
-            controlled.right = true;
-        }
+fn handle_movement(actions: Res<Input<InputAction>>, mut query: Query<&mut Controlled>) {
+    for mut controlled in query.iter_mut() {
+        let Controlled {
+            forward,
+            backward,
+            left,
+            right,
+            ..
+        } = controlled.as_mut();
+
+        for (action, moving) in [
+            (InputAction::MoveForward, forward),
+            (InputAction::MoveBackward, backward),
+            (InputAction::MoveLeft, left),
+            (InputAction::MoveRight, right),
+        ] {
+            if actions.just_pressed(action) {
+                *moving = true;
+            }
 
-        if keys.just_released(KeyCode::D) {
-            controlled.right = false;
+            if actions.just_released(action) {
+                *moving = false;
+            }
         }
     }
 }
 
-fn handle_jump(keys: Res<Input<KeyCode>>, mut hover_event: EventWriter<HoverEvent>) {
-    if keys.just_pressed(KeyCode::Space) {
+fn handle_jump(actions: Res<Input<InputAction>>, mut hover_event: EventWriter<HoverEvent>) {
+    if actions.just_pressed(InputAction::Hover) {
         hover_event.send(HoverEvent::Start);
     }
 
-    if keys.just_released(KeyCode::Space) {
+    if actions.just_released(InputAction::Hover) {
         hover_event.send(HoverEvent::Stop);
     }
 }
 
-fn handle_rotate(
-    mut mouse_button_events: EventReader<MouseButtonInput>,
+fn handle_rotate(actions: Res<Input<InputAction>>, mut query: Query<&mut Controlled>) {
+    for mut controlled in query.iter_mut() {
+        if actions.just_pressed(InputAction::Rotate) {
+            controlled.rotating = true;
+        }
+
+        if actions.just_released(InputAction::Rotate) {
+            controlled.rotating = false;
+        }
+    }
+}
+
+fn handle_look(
+    settings: Res<Settings>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
     mut query: Query<&mut Controlled>,
 ) {
-    for mouse_button_event in mouse_button_events.iter() {
-        if let MouseButton::Right = mouse_button_event.button {
-            for mut controlled in query.iter_mut() {
-                match mouse_button_event.state {
-                    ButtonState::Pressed => {
-                        controlled.rotating = true;
-                    }
-                    ButtonState::Released => {
-                        controlled.rotating = false;
-                    }
-                };
-            }
+    let delta: Vec2 = mouse_motion_events
+        .iter()
+        .fold(Vec2::ZERO, |delta, mouse_motion_event| {
+            delta + mouse_motion_event.delta
+        });
+
+    for mut controlled in query.iter_mut() {
+        if controlled.rotating && delta != Vec2::ZERO {
+            controlled.look += settings.mouse_sensitivity * delta;
         }
     }
 }
@@ -135,9 +160,11 @@ fn hide_cursor(
     }
 }
 
-pub fn handle_reset(keys: Res<Input<KeyCode>>, mut reset_event: EventWriter<ResetEvent>) {
-    if keys.just_pressed(KeyCode::R) {
-        reset_event.send(ResetEvent)
+pub fn handle_reset(actions: Res<Input<InputAction>>, mut reset_event: EventWriter<ResetEvent>) {
+    if actions.just_pressed(InputAction::Reset) {
+        reset_event.send(ResetEvent {
+            cause: ResetCause::Manual,
+        })
     }
 }
 
@@ -152,8 +179,9 @@ impl Default for ControlsConfig {
     }
 }
 
+/// The systems that turn the player's input into controls.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
-struct ActiveSet;
+pub struct ActiveSet;
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, SystemSet)]
 struct PassiveSet;
@@ -165,7 +193,14 @@ impl Plugin for ControlsPlugin {
         app.init_resource::<ControlsConfig>()
             .configure_set(ActiveSet.run_if(|config: Res<ControlsConfig>| config.enabled))
             .add_systems(
-                (handle_movement, handle_jump, handle_rotate, handle_reset).in_set(ActiveSet),
+                (
+                    handle_movement,
+                    handle_jump,
+                    handle_rotate,
+                    handle_look.after(handle_rotate),
+                    handle_reset,
+                )
+                    .in_set(ActiveSet),
             )
             .add_system(hide_cursor.after(handle_rotate).in_set(PassiveSet));
     }
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    reset::ResetEvent,
+    simulation::{SimulationEvents, SimulationSet, STEP_SECONDS},
+    GameState,
+};
+
+/// How far a crumbling wall shakes from its resting place.
+const SHAKE_DISTANCE: f32 = 0.04;
+
+/// How long a wall falls before it's hidden.
+const FALL_SECONDS: f32 = 3.0;
+
+fn default_delay() -> f32 {
+    1.0
+}
+
+/// Makes a wall fall away some time after the player first touches it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Crumble {
+    /// How long the wall shakes before it falls, in seconds.
+    #[serde(default = "default_delay")]
+    pub delay: f32,
+}
+
+impl Default for Crumble {
+    fn default() -> Self {
+        Self {
+            delay: default_delay(),
+        }
+    }
+}
+
+enum CrumbleState {
+    Intact,
+    Shaking { home: Transform, seconds: f32 },
+    Falling { home: Transform, seconds: f32 },
+}
+
+#[derive(Component)]
+pub struct Crumbling {
+    crumble: Crumble,
+    state: CrumbleState,
+}
+
+impl Crumbling {
+    pub fn new(crumble: Crumble) -> Self {
+        Self {
+            crumble,
+            state: CrumbleState::Intact,
+        }
+    }
+}
+
+/// Sent when the player touches a crumbling wall.
+pub struct CrumbleEvent {
+    pub entity: Entity,
+}
+
+fn handle_crumble_events(
+    mut crumble_events: EventReader<CrumbleEvent>,
+    mut query: Query<(&mut Crumbling, &Transform)>,
+) {
+    for CrumbleEvent { entity } in crumble_events.iter() {
+        if let Ok((mut crumbling, transform)) = query.get_mut(*entity) {
+            if let CrumbleState::Intact = crumbling.state {
+                debug!("wall {:?} started crumbling", entity);
+                crumbling.state = CrumbleState::Shaking {
+                    home: *transform,
+                    seconds: 0.0,
+                };
+            }
+        }
+    }
+}
+
+fn crumble(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Crumbling, &mut Transform, &mut Visibility)>,
+) {
+    let delta_seconds = STEP_SECONDS;
+
+    for (entity, mut crumbling, mut transform, mut visibility) in &mut query {
+        let delay = crumbling.crumble.delay;
+
+        match &mut crumbling.state {
+            CrumbleState::Intact => {}
+            CrumbleState::Shaking { home, seconds } => {
+                *seconds += delta_seconds;
+
+                let shake = Vec3::new((60.0 * *seconds).sin(), 0.0, (47.0 * *seconds).cos());
+                transform.translation = home.translation + SHAKE_DISTANCE * shake;
+
+                if *seconds >= delay {
+                    debug!("wall {:?} fell", entity);
+                    commands.entity(entity).insert(ColliderDisabled);
+                    crumbling.state = CrumbleState::Falling {
+                        home: *home,
+                        seconds: 0.0,
+                    };
+                }
+            }
+            CrumbleState::Falling { home, seconds } => {
+                *seconds += delta_seconds;
+
+                let distance = 0.5 * 9.81 * seconds.powi(2);
+                transform.translation = home.translation - distance * Vec3::Y;
+
+                if *seconds >= FALL_SECONDS {
+                    *visibility = Visibility::Hidden;
+                }
+            }
+        }
+    }
+}
+
+fn restore_crumbled(
+    mut commands: Commands,
+    mut reset_events: EventReader<ResetEvent>,
+    mut query: Query<(Entity, &mut Crumbling, &mut Transform, &mut Visibility)>,
+) {
+    if reset_events.iter().last().is_some() {
+        for (entity, mut crumbling, mut transform, mut visibility) in &mut query {
+            if let CrumbleState::Shaking { home, .. } | CrumbleState::Falling { home, .. } =
+                crumbling.state
+            {
+                *transform = home;
+                *visibility = Visibility::Inherited;
+                commands.entity(entity).remove::<ColliderDisabled>();
+                crumbling.state = CrumbleState::Intact;
+            }
+        }
+    }
+}
+
+pub struct CrumblePlugin;
+
+impl Plugin for CrumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_simulation_event::<CrumbleEvent>().add_systems(
+            (
+                handle_crumble_events,
+                crumble.after(handle_crumble_events),
+                restore_crumbled.after(crumble),
+            )
+                .in_set(SimulationSet::Logic)
+                .distributive_run_if(in_state(GameState::Playing))
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{AssetIo, AssetIoError, FileType, Metadata},
+    utils::BoxedFuture,
+};
+
+/// Every file in `assets/`, keyed by its path relative to `assets/`. Generated by `build.rs`.
+static ASSETS: &[(&str, &[u8])] = include!(concat!(env!("OUT_DIR"), "/embedded_assets.rs"));
+
+fn get(path: &Path) -> Option<&'static [u8]> {
+    ASSETS
+        .iter()
+        .find(|(asset_path, _)| Path::new(asset_path) == path)
+        .map(|(_, bytes)| *bytes)
+}
+
+fn is_embedded_dir(path: &Path) -> bool {
+    ASSETS.iter().any(|(asset_path, _)| {
+        Path::new(asset_path).starts_with(path) && Path::new(asset_path) != path
+    })
+}
+
+/**
+Serves the assets that were compiled into the binary.
+
+Files that exist in the `overrides` source take precedence over the embedded copies, so that a
+directory of assets next to the binary can still be used to modify the game.
+*/
+pub struct EmbeddedAssetIo {
+    overrides: Option<Box<dyn AssetIo>>,
+}
+
+impl EmbeddedAssetIo {
+    pub fn new(overrides: Option<Box<dyn AssetIo>>) -> Self {
+        Self { overrides }
+    }
+
+    fn overrides_file(&self, path: &Path) -> bool {
+        self.overrides
+            .as_ref()
+            .is_some_and(|overrides| overrides.is_file(path))
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            match &self.overrides {
+                Some(overrides) if self.overrides_file(path) => overrides.load_path(path).await,
+                _ => get(path)
+                    .map(|bytes| bytes.to_vec())
+                    .ok_or_else(|| AssetIoError::NotFound(path.to_path_buf())),
+            }
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let mut entries: Vec<PathBuf> = ASSETS
+            .iter()
+            .filter_map(|(asset_path, _)| {
+                let relative = Path::new(asset_path).strip_prefix(path).ok()?;
+                relative
+                    .components()
+                    .next()
+                    .map(|component| path.join(component))
+            })
+            .collect();
+
+        if let Some(overrides) = &self.overrides {
+            if let Ok(override_entries) = overrides.read_directory(path) {
+                entries.extend(override_entries);
+            }
+        }
+
+        if entries.is_empty() {
+            return Err(AssetIoError::NotFound(path.to_path_buf()));
+        }
+
+        entries.sort();
+        entries.dedup();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if get(path).is_some() {
+            Ok(Metadata::new(FileType::File))
+        } else if is_embedded_dir(path) {
+            Ok(Metadata::new(FileType::Directory))
+        } else if let Some(overrides) = &self.overrides {
+            overrides.get_metadata(path)
+        } else {
+            Err(AssetIoError::NotFound(path.to_path_buf()))
+        }
+    }
+
+    /*
+    Bevy's filesystem watcher only drives `FileAssetIo`, so hot reloading isn't available
+    when assets are embedded.
+    */
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
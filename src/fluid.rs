@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gravity::LocalGravity,
+    simulation::{SimulationSet, STEP_SECONDS},
+};
+
+fn default_drag() -> f32 {
+    2.0
+}
+
+fn default_buoyancy() -> f32 {
+    1.2
+}
+
+fn default_speed_multiplier() -> f32 {
+    0.5
+}
+
+fn default_hover_fuel_multiplier() -> f32 {
+    1.0
+}
+
+/// A volume of water, or any other fluid, that the player can move through.
+#[derive(Component, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Fluid {
+    /// The linear damping of anything inside the fluid.
+    #[serde(default = "default_drag")]
+    pub drag: f32,
+    /**
+    The upward push of the fluid, as a multiple of the local gravity. Above `1.0` the player
+    floats and below it they sink.
+    */
+    #[serde(default = "default_buoyancy")]
+    pub buoyancy: f32,
+    /// Scales the player's movement speed.
+    #[serde(default = "default_speed_multiplier")]
+    pub speed_multiplier: f32,
+    /// Scales the fuel used while hovering.
+    #[serde(default = "default_hover_fuel_multiplier")]
+    pub hover_fuel_multiplier: f32,
+}
+
+impl Default for Fluid {
+    fn default() -> Self {
+        Self {
+            drag: default_drag(),
+            buoyancy: default_buoyancy(),
+            speed_multiplier: default_speed_multiplier(),
+            hover_fuel_multiplier: default_hover_fuel_multiplier(),
+        }
+    }
+}
+
+#[derive(Bundle)]
+pub struct FluidBundle {
+    #[bundle]
+    pbr_bundle: PbrBundle,
+    collider: Collider,
+    active_events: ActiveEvents,
+    rigid_body: RigidBody,
+    sensor: Sensor,
+    fluid: Fluid,
+}
+
+impl FluidBundle {
+    pub fn new(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        position: Vec3,
+        size: Vec3,
+        fluid: Fluid,
+    ) -> Self {
+        Self {
+            pbr_bundle: PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(0.1, 0.4, 0.9, 0.35),
+                    alpha_mode: AlphaMode::Blend,
+                    perceptual_roughness: 0.1,
+                    ..default()
+                }),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            collider: Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            rigid_body: RigidBody::Fixed,
+            sensor: Sensor,
+            fluid,
+        }
+    }
+}
+
+/**
+The fluid that an entity is in, if any.
+
+While fluids overlap, the one that was entered most recently wins.
+*/
+#[derive(Component, Default)]
+pub struct Submerged {
+    pub fluid: Option<Fluid>,
+    fluids: Vec<Entity>,
+}
+
+impl Submerged {
+    pub fn speed_multiplier(&self) -> f32 {
+        self.fluid.map_or(1.0, |fluid| fluid.speed_multiplier)
+    }
+
+    pub fn hover_fuel_multiplier(&self) -> f32 {
+        self.fluid.map_or(1.0, |fluid| fluid.hover_fuel_multiplier)
+    }
+}
+
+fn track_fluids(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut submerged_query: Query<&mut Submerged>,
+    fluid_query: Query<(), With<Fluid>>,
+) {
+    for collision_event in collision_events.iter() {
+        let (entity1, entity2, started) = match collision_event {
+            CollisionEvent::Started(entity1, entity2, _) => (*entity1, *entity2, true),
+            CollisionEvent::Stopped(entity1, entity2, _) => (*entity1, *entity2, false),
+        };
+
+        let (submerged_entity, fluid) = if fluid_query.contains(entity2) {
+            (entity1, entity2)
+        } else if fluid_query.contains(entity1) {
+            (entity2, entity1)
+        } else {
+            continue;
+        };
+
+        if let Ok(mut submerged) = submerged_query.get_mut(submerged_entity) {
+            submerged.fluids.retain(|entered| *entered != fluid);
+
+            if started {
+                debug!("{:?} entered fluid {:?}", submerged_entity, fluid);
+                submerged.fluids.push(fluid);
+            } else {
+                debug!("{:?} exited fluid {:?}", submerged_entity, fluid);
+            }
+        }
+    }
+}
+
+fn update_submerged(fluid_query: Query<&Fluid>, mut query: Query<(&mut Submerged, &mut Damping)>) {
+    for (mut submerged, mut damping) in &mut query {
+        let fluid = submerged
+            .fluids
+            .iter()
+            .rev()
+            .find_map(|fluid| fluid_query.get(*fluid).ok())
+            .copied();
+
+        if submerged.fluid != fluid {
+            submerged.fluid = fluid;
+            damping.linear_damping = fluid.map_or(0.0, |fluid| fluid.drag);
+        }
+    }
+}
+
+fn apply_buoyancy(mut query: Query<(&Submerged, &LocalGravity, &mut Velocity)>) {
+    for (submerged, local_gravity, mut velocity) in &mut query {
+        if let Some(fluid) = submerged.fluid {
+            velocity.linvel -= STEP_SECONDS * fluid.buoyancy * local_gravity.value;
+        }
+    }
+}
+
+pub struct FluidPlugin;
+
+impl Plugin for FluidPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                track_fluids,
+                update_submerged.after(track_fluids),
+                apply_buoyancy.after(update_submerged),
+            )
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
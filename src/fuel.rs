@@ -1,32 +1,234 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    hover::Hovering,
+    jump::Grounded,
+    simulation::{SimulationSet, STEP_SECONDS},
+};
+
+fn default_regeneration_rate() -> f32 {
+    0.25
+}
+
+fn default_cooldown_seconds() -> f32 {
+    2.0
+}
+
+fn default_cooling_rate() -> f32 {
+    0.3
+}
+
+/**
+How the player's fuel is used up and refilled.
+
+Every model keeps fuel between `0.0` and `1.0`, which hovering uses up and fuel balls add to.
+*/
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FuelModel {
+    /// Fuel only comes from fuel balls and triggers.
+    #[default]
+    Tank,
+    /// Fuel refills while the player stands on the ground without hovering.
+    Regenerating {
+        /// Fuel regained per second.
+        #[serde(default = "default_regeneration_rate")]
+        rate: f32,
+    },
+    /// Running out of fuel stops hovering until the tank refills, some time later.
+    Cooldown {
+        #[serde(default = "default_cooldown_seconds")]
+        seconds: f32,
+    },
+    /**
+    Hovering heats the player up instead of using fuel, and they cool down whenever they aren't
+    hovering. Overheating stops hovering until they've cooled down completely.
+
+    The fuel is how far the player is from overheating.
+    */
+    Overheat {
+        /// Heat lost per second.
+        #[serde(default = "default_cooling_rate")]
+        cooling_rate: f32,
+    },
+}
+
+impl FuelModel {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The model called `name` in level files, such as `"overheat"`, with its default settings.
+    pub fn from_name(name: &str) -> Result<Self, String> {
+        serde_json::from_value(serde_json::json!({ "type": name }))
+            .map_err(|error| error.to_string())
+    }
+}
+
+/**
+Replaces the fuel model of every level, for game modes that play them differently.
+
+It's chosen with `--fuel-model`, and wins over the model in the level's fuel budget.
+*/
+#[derive(Resource, Default)]
+pub struct FuelModelOverride(pub Option<FuelModel>);
 
 #[derive(Component, Clone, Copy)]
 pub struct Fuel {
     pub value: f32,
+    /// The total fuel used since the player was spawned or reset.
+    pub used: f32,
+    pub model: FuelModel,
+    /// Whether the model has stopped the fuel from being used, after running out.
+    pub locked: bool,
+    seconds_locked: f32,
+}
+
+impl Fuel {
+    pub fn new(value: f32, model: FuelModel) -> Self {
+        Self {
+            value,
+            used: 0.0,
+            model,
+            locked: false,
+            seconds_locked: 0.0,
+        }
+    }
+
+    /// Whether there's fuel that can be used to hover or jump.
+    pub fn available(&self) -> bool {
+        self.value > 0.0 && !self.locked
+    }
+
+    pub fn unlock(&mut self) {
+        self.locked = false;
+        self.seconds_locked = 0.0;
+    }
+
+    pub fn changed(&self) -> FuelChanged {
+        FuelChanged {
+            new_value: self.value,
+            model: self.model,
+            locked: self.locked,
+        }
+    }
 }
 
 pub struct FuelChanged {
     pub new_value: f32,
+    pub model: FuelModel,
+    pub locked: bool,
 }
 
 pub fn subtract_fuel(fuel: &mut Fuel, amount: f32, fuel_changed: &mut EventWriter<FuelChanged>) {
-    fuel.value = (fuel.value - amount).clamp(0.0, 1.0);
-    fuel_changed.send(FuelChanged {
-        new_value: fuel.value,
-    });
+    let value = (fuel.value - amount).clamp(0.0, 1.0);
+    fuel.used += fuel.value - value;
+    fuel.value = value;
+    fuel_changed.send(fuel.changed());
 }
 
 pub fn add_fuel(fuel: &mut Fuel, amount: f32, fuel_changed: &mut EventWriter<FuelChanged>) {
     fuel.value = (fuel.value + amount).clamp(0.0, 1.0);
-    fuel_changed.send(FuelChanged {
-        new_value: fuel.value,
-    });
+    fuel_changed.send(fuel.changed());
+}
+
+fn override_fuel_model(
+    fuel_model_override: Res<FuelModelOverride>,
+    mut query: Query<&mut Fuel, Added<Fuel>>,
+    mut fuel_changed: EventWriter<FuelChanged>,
+) {
+    if let Some(model) = fuel_model_override.0 {
+        for mut fuel in &mut query {
+            fuel.model = model;
+            fuel_changed.send(fuel.changed());
+        }
+    }
+}
+
+fn run_fuel_models(
+    mut query: Query<(&mut Fuel, &Hovering, Option<&Grounded>)>,
+    mut fuel_changed: EventWriter<FuelChanged>,
+) {
+    for (mut fuel, hovering, grounded) in &mut query {
+        match fuel.model {
+            FuelModel::Tank => {}
+            FuelModel::Regenerating { rate } => {
+                let grounded = matches!(grounded, Some(Grounded { value: true, .. }));
+
+                if grounded && !hovering.value && fuel.value < 1.0 {
+                    add_fuel(&mut fuel, STEP_SECONDS * rate, &mut fuel_changed);
+                }
+            }
+            FuelModel::Cooldown { seconds } => {
+                if !fuel.locked && fuel.value <= 0.0 {
+                    debug!("fuel ran out, cooling down");
+                    fuel.locked = true;
+                    fuel_changed.send(fuel.changed());
+                } else if fuel.locked {
+                    fuel.seconds_locked += STEP_SECONDS;
+
+                    if fuel.value > 0.0 {
+                        /* Picking up fuel ends the cooldown early. */
+                        fuel.unlock();
+                        fuel_changed.send(fuel.changed());
+                    } else if fuel.seconds_locked >= seconds {
+                        fuel.unlock();
+                        add_fuel(&mut fuel, 1.0, &mut fuel_changed);
+                    }
+                }
+            }
+            FuelModel::Overheat { cooling_rate } => {
+                if !fuel.locked && fuel.value <= 0.0 {
+                    debug!("overheated");
+                    fuel.locked = true;
+                    fuel_changed.send(fuel.changed());
+                }
+
+                if !hovering.value && fuel.value < 1.0 {
+                    add_fuel(&mut fuel, STEP_SECONDS * cooling_rate, &mut fuel_changed);
+                }
+
+                if fuel.locked && fuel.value >= 1.0 {
+                    fuel.unlock();
+                    fuel_changed.send(fuel.changed());
+                }
+            }
+        }
+    }
 }
 
 pub struct FuelPlugin;
 
 impl Plugin for FuelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<FuelChanged>();
+        app.add_event::<FuelChanged>()
+            .init_resource::<FuelModelOverride>()
+            .add_systems(
+                (
+                    override_fuel_model,
+                    run_fuel_models.after(override_fuel_model),
+                )
+                    .in_set(SimulationSet::Logic)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn models_are_named_as_in_level_files() {
+        assert_eq!(FuelModel::from_name("tank"), Ok(FuelModel::Tank));
+        assert_eq!(
+            FuelModel::from_name("overheat"),
+            Ok(FuelModel::Overheat {
+                cooling_rate: default_cooling_rate()
+            })
+        );
+        assert!(FuelModel::from_name("Overheat").is_err());
+        assert!(FuelModel::from_name("nonsense").is_err());
     }
 }
@@ -3,7 +3,11 @@ use std::f32::consts::PI;
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
 
-use crate::fuel::{add_fuel, Fuel, FuelChanged};
+use crate::{
+    fuel::{add_fuel, Fuel, FuelChanged},
+    simulation::SimulationSet,
+    stats::FuelCollectedEvent,
+};
 
 #[derive(Component)]
 pub struct FuelBall {
@@ -54,6 +58,7 @@ fn refuel(
     mut fuel_query: Query<&mut Fuel, Without<FuelBall>>,
     mut ball_query: Query<(&FuelBall, &mut Visibility)>,
     mut fuel_changed: EventWriter<FuelChanged>,
+    mut fuel_collected: EventWriter<FuelCollectedEvent>,
 ) {
     for collision_event in collision_events.iter() {
         if let CollisionEvent::Started(entity1, entity2, _) = collision_event {
@@ -68,6 +73,9 @@ fn refuel(
                 ball_query.get_mut(ball_entity),
             ) {
                 add_fuel(&mut fuel, refuel_ball.amount, &mut fuel_changed);
+                fuel_collected.send(FuelCollectedEvent {
+                    amount: refuel_ball.amount,
+                });
                 *visibility = Visibility::Hidden;
                 commands.entity(ball_entity).remove::<Collider>();
             }
@@ -87,6 +95,11 @@ pub struct FuelBallPlugin;
 
 impl Plugin for FuelBallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(refuel).add_system(rotate);
+        app.add_system(
+            refuel
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        )
+        .add_system(rotate);
     }
 }
@@ -0,0 +1,126 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fuel::{Fuel, FuelModel},
+    hover::{end_hover, Hovering},
+    load_level::CurrentLevel,
+    pause::PauseEvent,
+    player::Player,
+    reset::ResetEvent,
+    simulation::SimulationSet,
+    ui::{self, UI},
+    GameState,
+};
+
+fn default_starting_fuel() -> f32 {
+    1.0
+}
+
+fn default_fuel_balls() -> bool {
+    true
+}
+
+/// Limits on the fuel that the player can use in a level.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct FuelBudget {
+    #[serde(default = "default_starting_fuel")]
+    pub starting_fuel: f32,
+    /// Whether the level's fuel balls are spawned.
+    #[serde(default = "default_fuel_balls")]
+    pub fuel_balls: bool,
+    /// The player fails the level if they use more fuel than this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fuel_used: Option<f32>,
+    #[serde(default, skip_serializing_if = "FuelModel::is_default")]
+    pub model: FuelModel,
+}
+
+impl Default for FuelBudget {
+    fn default() -> Self {
+        Self {
+            starting_fuel: default_starting_fuel(),
+            fuel_balls: default_fuel_balls(),
+            max_fuel_used: None,
+            model: FuelModel::default(),
+        }
+    }
+}
+
+impl FuelBudget {
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Marks a player that has broken the level's fuel budget, until the level is reset.
+#[derive(Component)]
+struct OverBudget;
+
+#[allow(clippy::type_complexity)]
+fn enforce_fuel_budget(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut ui: ResMut<UI>,
+    current_level: Res<CurrentLevel>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut query: Query<
+        (Entity, &Fuel, &mut Hovering, &mut ExternalForce),
+        (With<Player>, Without<OverBudget>),
+    >,
+) {
+    let max_fuel_used = match current_level.level.fuel_budget.max_fuel_used {
+        Some(max_fuel_used) => max_fuel_used,
+        None => return,
+    };
+
+    for (entity, fuel, mut hovering, mut external_force) in &mut query {
+        if fuel.used > max_fuel_used {
+            debug!(
+                "player {:?} used {} fuel of {}",
+                entity, fuel.used, max_fuel_used
+            );
+
+            commands.entity(entity).insert(OverBudget);
+            end_hover(&mut hovering, &mut external_force);
+            pause_event.send(PauseEvent::Pause);
+
+            ui::overlay::level_failed::display(
+                &asset_server,
+                &mut commands,
+                &mut ui,
+                &[
+                    "fuel budget exceeded".to_string(),
+                    format!("this level allows {:.0}% fuel", 100.0 * max_fuel_used),
+                ],
+            );
+        }
+    }
+}
+
+fn clear_over_budget(
+    mut commands: Commands,
+    mut reset_events: EventReader<ResetEvent>,
+    query: Query<Entity, With<OverBudget>>,
+) {
+    if reset_events.iter().last().is_some() {
+        for entity in &query {
+            commands.entity(entity).remove::<OverBudget>();
+        }
+    }
+}
+
+pub struct FuelBudgetPlugin;
+
+impl Plugin for FuelBudgetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (enforce_fuel_budget, clear_over_budget)
+                .in_set(SimulationSet::Logic)
+                .distributive_run_if(in_state(GameState::Playing))
+                .distributive_run_if(resource_exists::<CurrentLevel>())
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
@@ -0,0 +1,369 @@
+use std::path::Path;
+
+use bevy::{prelude::*, window::FileDragAndDrop};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    load_level::{CurrentLevel, InCurrentLevel},
+    player::{Player, CAPSULE_DEPTH, CAPSULE_RADIUS},
+    replay::not_watching_replay,
+    save,
+    simulation::SimulationSet,
+    timer::{LevelTimer, NewBestEvent},
+    GameState,
+};
+
+const GHOST_VERSION: u32 = 1;
+
+/// The most frames a saved ghost keeps. Storage on the web is small, so ghosts are thinned there.
+#[cfg(target_family = "wasm")]
+const MAX_SAVED_FRAMES: usize = 1500;
+#[cfg(not(target_family = "wasm"))]
+const MAX_SAVED_FRAMES: usize = usize::MAX;
+
+#[derive(Serialize, Deserialize, Clone, Copy)]
+pub struct GhostFrame {
+    /// The level timer when this frame was recorded.
+    pub seconds: f32,
+    pub translation: Vec3,
+    pub rotation: Quat,
+}
+
+/// A recording of the player's run of a level.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Ghost {
+    pub version: u32,
+    pub level_path: String,
+    /// The run's time, in seconds.
+    pub seconds: f32,
+    pub frames: Vec<GhostFrame>,
+}
+
+impl Ghost {
+    /// The pose at `seconds` into the run, interpolated between the recorded frames.
+    pub fn sample(&self, seconds: f32) -> Option<Transform> {
+        let next_index = self
+            .frames
+            .partition_point(|frame| frame.seconds <= seconds);
+
+        let (previous, next) = match (
+            next_index.checked_sub(1).map(|index| &self.frames[index]),
+            self.frames.get(next_index),
+        ) {
+            (Some(previous), Some(next)) => (previous, next),
+            (Some(frame), None) | (None, Some(frame)) => (frame, frame),
+            (None, None) => return None,
+        };
+
+        let span = next.seconds - previous.seconds;
+        let t = if span > 0.0 {
+            ((seconds - previous.seconds) / span).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        Some(
+            Transform::from_translation(previous.translation.lerp(next.translation, t))
+                .with_rotation(previous.rotation.slerp(next.rotation, t)),
+        )
+    }
+}
+
+/// Where ghosts of `level_path` are saved. Imported ghosts are kept apart from the player's own.
+fn ghost_file(level_path: &str, imported: bool) -> String {
+    let name = level_path.replace(['/', '\\'], "_");
+    let dir = if imported {
+        "ghosts/imported"
+    } else {
+        "ghosts"
+    };
+    format!("{}/{}", dir, name)
+}
+
+/**
+At most `max_frames` of `frames`, spread evenly and keeping the first and last, which playback
+interpolates between.
+*/
+fn thin(frames: &[GhostFrame], max_frames: usize) -> Vec<GhostFrame> {
+    if frames.len() <= max_frames {
+        return frames.to_vec();
+    }
+
+    let last_index = frames.len() - 1;
+    let step = (last_index + max_frames - 2) / (max_frames - 1);
+
+    let mut thinned: Vec<GhostFrame> = frames.iter().step_by(step).copied().collect();
+    if !last_index.is_multiple_of(step) {
+        thinned.push(frames[last_index]);
+    }
+    thinned
+}
+
+fn save_ghost(name: &str, ghost: &Ghost) {
+    save::write_compact(
+        name,
+        &Ghost {
+            version: ghost.version,
+            level_path: ghost.level_path.clone(),
+            seconds: ghost.seconds,
+            frames: thin(&ghost.frames, MAX_SAVED_FRAMES),
+        },
+    );
+}
+
+/// The frames of the current run.
+#[derive(Resource, Default)]
+struct Recording {
+    frames: Vec<GhostFrame>,
+}
+
+/*
+Frames are stamped with the level timer rather than counted, so that the ghost stays in step with
+the timer while the game is paused and a restarted run can be recognised by the timer going back.
+*/
+fn record(
+    timer: Res<LevelTimer>,
+    mut recording: ResMut<Recording>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    let last_seconds = recording.frames.last().map(|frame| frame.seconds);
+
+    if last_seconds.is_some_and(|last_seconds| timer.seconds < last_seconds) {
+        recording.frames.clear();
+    }
+
+    if timer.stopped || last_seconds == Some(timer.seconds) {
+        return;
+    }
+
+    for transform in &player_query {
+        recording.frames.push(GhostFrame {
+            seconds: timer.seconds,
+            translation: transform.translation,
+            rotation: transform.rotation,
+        });
+    }
+}
+
+fn save_best(mut new_best_events: EventReader<NewBestEvent>, recording: Res<Recording>) {
+    for NewBestEvent {
+        level_path,
+        seconds,
+    } in new_best_events.iter()
+    {
+        debug!("saving ghost of {:?}", level_path);
+
+        save_ghost(
+            &ghost_file(level_path, false),
+            &Ghost {
+                version: GHOST_VERSION,
+                level_path: level_path.clone(),
+                seconds: *seconds,
+                frames: recording.frames.clone(),
+            },
+        );
+    }
+}
+
+/// Requests that the ghost file at `path` is imported.
+pub struct ImportGhostEvent {
+    pub path: std::path::PathBuf,
+}
+
+/// Import ghost files that are dropped onto the window.
+fn import_ghosts(
+    mut file_drag_and_drop_events: EventReader<FileDragAndDrop>,
+    mut import_event: EventWriter<ImportGhostEvent>,
+) {
+    for event in file_drag_and_drop_events.iter() {
+        if let FileDragAndDrop::DroppedFile { path_buf, .. } = event {
+            import_event.send(ImportGhostEvent {
+                path: path_buf.clone(),
+            });
+        }
+    }
+}
+
+fn read_ghost(path: &Path) -> Result<Ghost, String> {
+    let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+    let ghost: Ghost = serde_json::from_slice(&bytes).map_err(|error| error.to_string())?;
+
+    if ghost.version != GHOST_VERSION {
+        return Err(format!("unsupported ghost version {}", ghost.version));
+    }
+
+    Ok(ghost)
+}
+
+/// Keep a copy of an imported ghost, replacing any earlier import for the same level.
+fn handle_import_ghost(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut import_events: EventReader<ImportGhostEvent>,
+    current_level: Option<Res<CurrentLevel>>,
+    ghost_query: Query<(Entity, &GhostPlayback)>,
+) {
+    for ImportGhostEvent { path } in import_events.iter() {
+        let ghost = match read_ghost(path) {
+            Ok(ghost) => ghost,
+            Err(error) => {
+                warn!("couldn't import ghost {:?}: {}", path, error);
+                continue;
+            }
+        };
+
+        info!("imported ghost of {:?} from {:?}", ghost.level_path, path);
+        save_ghost(&ghost_file(&ghost.level_path, true), &ghost);
+
+        if let Some(current_level) = &current_level {
+            if current_level.path == ghost.level_path {
+                for (entity, playback) in &ghost_query {
+                    if playback.imported {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                }
+
+                spawn_ghost(&mut commands, &mut meshes, &mut materials, ghost, true);
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct GhostPlayback {
+    ghost: Ghost,
+    imported: bool,
+}
+
+fn spawn_ghost(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    ghost: Ghost,
+    imported: bool,
+) {
+    let color = if imported {
+        Color::rgba(0.3, 0.8, 1.0, 0.3)
+    } else {
+        Color::rgba(1.0, 1.0, 1.0, 0.3)
+    };
+
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Capsule {
+                radius: CAPSULE_RADIUS,
+                depth: CAPSULE_DEPTH,
+                ..default()
+            })),
+            material: materials.add(StandardMaterial {
+                base_color: color,
+                alpha_mode: AlphaMode::Blend,
+                ..default()
+            }),
+            transform: ghost.sample(0.0).unwrap_or_default(),
+            ..default()
+        },
+        GhostPlayback { ghost, imported },
+        InCurrentLevel::NoLocation,
+    ));
+}
+
+fn spawn_ghosts(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    current_level: Res<CurrentLevel>,
+    player_query: Query<(), Added<Player>>,
+) {
+    if player_query.is_empty() {
+        return;
+    }
+
+    for imported in [false, true] {
+        let ghost: Option<Ghost> = save::read(&ghost_file(&current_level.path, imported));
+
+        if let Some(ghost) = ghost.filter(|ghost| ghost.version == GHOST_VERSION) {
+            spawn_ghost(&mut commands, &mut meshes, &mut materials, ghost, imported);
+        }
+    }
+}
+
+fn play_ghosts(timer: Res<LevelTimer>, mut query: Query<(&GhostPlayback, &mut Transform)>) {
+    for (playback, mut transform) in &mut query {
+        if let Some(sample) = playback.ghost.sample(timer.seconds) {
+            *transform = sample;
+        }
+    }
+}
+
+pub struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recording>()
+            .add_event::<ImportGhostEvent>()
+            .add_system(
+                record
+                    .in_set(SimulationSet::Record)
+                    .run_if(in_state(GameState::Playing))
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(import_ghosts)
+            .add_system(handle_import_ghost.after(import_ghosts))
+            .add_systems(
+                (spawn_ghosts, play_ghosts)
+                    .in_set(OnUpdate(GameState::Playing))
+                    .distributive_run_if(resource_exists::<CurrentLevel>()),
+            )
+            .add_system(
+                save_best
+                    .in_set(OnUpdate(GameState::Playing))
+                    .run_if(resource_exists::<CurrentLevel>())
+                    .run_if(not_watching_replay),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frames(count: usize) -> Vec<GhostFrame> {
+        (0..count)
+            .map(|index| GhostFrame {
+                seconds: index as f32,
+                translation: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+            })
+            .collect()
+    }
+
+    fn seconds(frames: &[GhostFrame]) -> Vec<f32> {
+        frames.iter().map(|frame| frame.seconds).collect()
+    }
+
+    #[test]
+    fn short_ghosts_arent_thinned() {
+        assert_eq!(seconds(&thin(&frames(5), 5)), [0.0, 1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn thinned_ghosts_keep_the_first_and_last_frames() {
+        assert_eq!(seconds(&thin(&frames(9), 5)), [0.0, 2.0, 4.0, 6.0, 8.0]);
+        assert_eq!(seconds(&thin(&frames(10), 5)), [0.0, 3.0, 6.0, 9.0]);
+        assert_eq!(seconds(&thin(&frames(11), 4)), [0.0, 4.0, 8.0, 10.0]);
+
+        for count in 0..50 {
+            for max_frames in 2..10 {
+                let thinned = thin(&frames(count), max_frames);
+
+                assert!(thinned.len() <= max_frames);
+                assert_eq!(
+                    thinned.last().map(|frame| frame.seconds),
+                    count.checked_sub(1).map(|index| index as f32)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controls::Forward,
+    player::Player,
+    simulation::{SimulationSet, STEP_SECONDS},
+};
+
+/// How quickly the player turns to stand against a new gravity direction, per second.
+const ALIGN_RATE: f32 = 5.0;
+
+/// The gravity inside a volume. It replaces the world's gravity while the player is inside.
+#[derive(Component, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct GravityZone {
+    pub direction: Vec3,
+    pub strength: f32,
+}
+
+impl Default for GravityZone {
+    fn default() -> Self {
+        Self {
+            direction: -Vec3::Y,
+            strength: 9.81,
+        }
+    }
+}
+
+impl GravityZone {
+    pub fn gravity(&self) -> Vec3 {
+        self.direction.try_normalize().unwrap_or(-Vec3::Y) * self.strength
+    }
+}
+
+#[derive(Bundle)]
+pub struct GravityZoneBundle {
+    #[bundle]
+    pbr_bundle: PbrBundle,
+    collider: Collider,
+    active_events: ActiveEvents,
+    rigid_body: RigidBody,
+    sensor: Sensor,
+    gravity_zone: GravityZone,
+}
+
+impl GravityZoneBundle {
+    pub fn new(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        position: Vec3,
+        size: Vec3,
+        gravity_zone: GravityZone,
+    ) -> Self {
+        Self {
+            pbr_bundle: PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(0.6, 0.3, 1.0, 0.1),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            collider: Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            rigid_body: RigidBody::Fixed,
+            sensor: Sensor,
+            gravity_zone,
+        }
+    }
+}
+
+/**
+The gravity acting on an entity, which is applied by this module instead of by rapier.
+
+While zones overlap, the one that was entered most recently wins.
+*/
+#[derive(Component, Default)]
+pub struct LocalGravity {
+    pub value: Vec3,
+    zones: Vec<Entity>,
+}
+
+impl LocalGravity {
+    /// The direction that hovering and jumping push towards.
+    pub fn up(&self) -> Vec3 {
+        (-self.value).try_normalize().unwrap_or(Vec3::Y)
+    }
+
+    /// How strong this gravity is compared to the world's, for scaling forces that oppose it.
+    pub fn scale(&self, world_gravity: Vec3) -> f32 {
+        match world_gravity.length() {
+            length if length > 0.0 => self.value.length() / length,
+            _ => 1.0,
+        }
+    }
+}
+
+fn track_gravity_zones(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut player_query: Query<&mut LocalGravity, With<Player>>,
+    zone_query: Query<(), With<GravityZone>>,
+) {
+    for collision_event in collision_events.iter() {
+        let (entity1, entity2, started) = match collision_event {
+            CollisionEvent::Started(entity1, entity2, _) => (*entity1, *entity2, true),
+            CollisionEvent::Stopped(entity1, entity2, _) => (*entity1, *entity2, false),
+        };
+
+        let (player, zone) = if zone_query.contains(entity2) {
+            (entity1, entity2)
+        } else if zone_query.contains(entity1) {
+            (entity2, entity1)
+        } else {
+            continue;
+        };
+
+        if let Ok(mut local_gravity) = player_query.get_mut(player) {
+            local_gravity.zones.retain(|entered| *entered != zone);
+
+            if started {
+                debug!("player entered gravity zone {:?}", zone);
+                local_gravity.zones.push(zone);
+            } else {
+                debug!("player exited gravity zone {:?}", zone);
+            }
+        }
+    }
+}
+
+fn update_local_gravity(
+    rapier_configuration: Res<RapierConfiguration>,
+    zone_query: Query<&GravityZone>,
+    mut query: Query<&mut LocalGravity>,
+) {
+    for mut local_gravity in &mut query {
+        let value = local_gravity
+            .zones
+            .iter()
+            .rev()
+            .find_map(|zone| zone_query.get(*zone).ok())
+            .map_or(rapier_configuration.gravity, GravityZone::gravity);
+
+        if local_gravity.value != value {
+            local_gravity.value = value;
+        }
+    }
+}
+
+/*
+Rapier only has one gravity for the whole world, so bodies with `LocalGravity` have a
+`GravityScale` of zero and are accelerated here instead.
+*/
+fn apply_local_gravity(mut query: Query<(&LocalGravity, &mut Velocity)>) {
+    for (local_gravity, mut velocity) in &mut query {
+        velocity.linvel += STEP_SECONDS * local_gravity.value;
+    }
+}
+
+/// Turn the player, and so its camera, so that its feet point along the local gravity.
+fn align_to_gravity(mut query: Query<(&LocalGravity, &mut Transform, &mut Forward), With<Player>>) {
+    let t = (ALIGN_RATE * STEP_SECONDS).min(1.0);
+
+    for (local_gravity, mut transform, mut forward) in &mut query {
+        let arc = Quat::from_rotation_arc(transform.up(), local_gravity.up());
+
+        if arc.angle_between(Quat::IDENTITY) > f32::EPSILON {
+            let rotation = Quat::IDENTITY.slerp(arc, t);
+            transform.rotation = (rotation * transform.rotation).normalize();
+            forward.value = rotation * forward.value;
+        }
+    }
+}
+
+pub struct GravityPlugin;
+
+impl Plugin for GravityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                track_gravity_zones,
+                update_local_gravity.after(track_gravity_zones),
+                apply_local_gravity.after(update_local_gravity),
+                align_to_gravity.after(update_local_gravity),
+            )
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
@@ -1,11 +1,19 @@
 use bevy::prelude::*;
 use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
+    fluid::Submerged,
     fuel::{subtract_fuel, Fuel, FuelChanged},
-    jump::JumpImpulse,
+    gravity::LocalGravity,
+    respawn::Dying,
+    simulation::{SimulationEvents, SimulationSet, STEP_SECONDS},
 };
 
+/// The force that hovering applies against the world's gravity.
+const HOVER_FORCE: f32 = 12.;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum HoverEvent {
     Start,
     Stop,
@@ -16,60 +24,52 @@ pub struct Hovering {
     pub value: bool,
 }
 
+/// The hover force for `local_gravity`, which is scaled so that hovering feels the same in any gravity.
+pub fn hover_force(local_gravity: &LocalGravity, world_gravity: Vec3) -> Vec3 {
+    HOVER_FORCE * local_gravity.scale(world_gravity) * local_gravity.up()
+}
+
+/// Start hovering, if there's fuel. Jumping off the ground is handled by [`crate::jump`].
 pub fn start_hover(
     fuel: &Fuel,
     hovering: &mut Hovering,
-    external_impulse: &mut ExternalImpulse,
-    jump_impulse: &JumpImpulse,
     external_force: &mut ExternalForce,
+    local_gravity: &LocalGravity,
+    world_gravity: Vec3,
 ) {
-    if fuel.value > 0.0 {
+    if fuel.available() {
         hovering.value = true;
-        external_impulse.impulse = jump_impulse.value;
-        external_force.force = 12. * Vec3::Y;
+        external_force.force = hover_force(local_gravity, world_gravity);
     }
 }
 
-pub fn end_hover(
-    hovering: &mut Hovering,
-    external_impulse: &mut ExternalImpulse,
-    external_force: &mut ExternalForce,
-) {
+pub fn end_hover(hovering: &mut Hovering, external_force: &mut ExternalForce) {
     hovering.value = false;
-    external_impulse.impulse = Vec3::ZERO;
     external_force.force = Vec3::ZERO;
 }
 
+/* Pressing hover while dying skips to the respawn instead, so a dying player can't hover. */
 fn handle_hover_events(
     mut events: EventReader<HoverEvent>,
-    mut query: Query<(
-        &mut Hovering,
-        &Fuel,
-        &JumpImpulse,
-        &mut ExternalImpulse,
-        &mut ExternalForce,
-    )>,
+    rapier_configuration: Res<RapierConfiguration>,
+    mut query: Query<(&mut Hovering, &Fuel, &mut ExternalForce, &LocalGravity), Without<Dying>>,
 ) {
     for event in events.iter() {
         match event {
             HoverEvent::Start => {
-                for (mut hovering, fuel, jump_impulse, mut external_impulse, mut external_force) in
-                    query.iter_mut()
-                {
+                for (mut hovering, fuel, mut external_force, local_gravity) in query.iter_mut() {
                     start_hover(
                         fuel,
                         &mut hovering,
-                        &mut external_impulse,
-                        jump_impulse,
                         &mut external_force,
+                        local_gravity,
+                        rapier_configuration.gravity,
                     );
                 }
             }
             HoverEvent::Stop => {
-                for (mut hovering, _, _, mut external_impulse, mut external_force) in
-                    query.iter_mut()
-                {
-                    end_hover(&mut hovering, &mut external_impulse, &mut external_force);
+                for (mut hovering, _, mut external_force, _) in query.iter_mut() {
+                    end_hover(&mut hovering, &mut external_force);
                 }
             }
         }
@@ -77,32 +77,50 @@ fn handle_hover_events(
 }
 
 fn use_fuel_to_hover(
-    time: Res<Time>,
     mut query: Query<(
         &mut Hovering,
         &mut Fuel,
-        &mut ExternalImpulse,
         &mut ExternalForce,
+        Option<&Submerged>,
     )>,
     mut fuel_changed: EventWriter<FuelChanged>,
 ) {
-    for (mut hovering, mut fuel, mut external_impulse, mut external_force) in &mut query {
+    for (mut hovering, mut fuel, mut external_force, submerged) in &mut query {
         if hovering.value {
-            subtract_fuel(&mut fuel, time.delta_seconds() * 0.1, &mut fuel_changed);
+            let rate = 0.1 * submerged.map_or(1.0, Submerged::hover_fuel_multiplier);
+            subtract_fuel(&mut fuel, STEP_SECONDS * rate, &mut fuel_changed);
 
-            if fuel.value <= 0. {
-                end_hover(&mut hovering, &mut external_impulse, &mut external_force)
+            if !fuel.available() {
+                end_hover(&mut hovering, &mut external_force)
             }
         }
     }
 }
 
+/// Keep hovering against gravity as the player moves between gravity zones.
+fn follow_local_gravity(
+    rapier_configuration: Res<RapierConfiguration>,
+    mut query: Query<(&Hovering, &LocalGravity, &mut ExternalForce), Changed<LocalGravity>>,
+) {
+    for (hovering, local_gravity, mut external_force) in &mut query {
+        if hovering.value {
+            external_force.force = hover_force(local_gravity, rapier_configuration.gravity);
+        }
+    }
+}
+
 pub struct HoverPlugin;
 
 impl Plugin for HoverPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<HoverEvent>()
-            .add_system(handle_hover_events)
-            .add_system(use_fuel_to_hover);
+        app.add_simulation_event::<HoverEvent>().add_systems(
+            (
+                handle_hover_events,
+                use_fuel_to_hover.after(handle_hover_events),
+                follow_local_gravity.after(handle_hover_events),
+            )
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
     }
 }
@@ -1,6 +1,187 @@
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
 
+use crate::{
+    fuel::Fuel,
+    gravity::LocalGravity,
+    hover::HoverEvent,
+    player::{CAPSULE_DEPTH, CAPSULE_RADIUS},
+    reset::ResetEvent,
+    respawn::Dying,
+    simulation::{SimulationSet, STEP_SECONDS},
+};
+
+/// How far below the player the ground can be while they still count as standing on it.
+const GROUND_DISTANCE: f32 = 0.1;
+
+/// How much smaller the shape that's cast to find the ground is than the player, so that it
+/// doesn't catch on walls the player is leaning against.
+const GROUND_CAST_MARGIN: f32 = 0.05;
+
+/// How long after walking off a ledge the player can still jump.
+const COYOTE_SECONDS: f32 = 0.1;
+
+/// How long before landing a jump can be pressed and still happen.
+const JUMP_BUFFER_SECONDS: f32 = 0.15;
+
+/// How long after jumping before the player can jump again, while the ground is still in reach.
+const JUMP_COOLDOWN_SECONDS: f32 = 0.2;
+
+/**
+The impulse applied when jumping.
+
+It's written for an "up" of `Y`, and is turned to oppose the local gravity when it's applied.
+*/
 #[derive(Component)]
 pub struct JumpImpulse {
     pub value: Vec3,
 }
+
+/// Whether the player is standing on something.
+#[derive(Component, Default)]
+pub struct Grounded {
+    pub value: bool,
+    seconds_airborne: f32,
+}
+
+/// A jump that's waiting for the player to be able to jump.
+#[derive(Component, Default)]
+pub struct Jumping {
+    buffered_seconds: Option<f32>,
+    cooldown_seconds: f32,
+}
+
+fn detect_ground(
+    rapier_context: Res<RapierContext>,
+    mut query: Query<(Entity, &Transform, &LocalGravity, &mut Grounded)>,
+) {
+    let shape = Collider::ball(CAPSULE_RADIUS - GROUND_CAST_MARGIN);
+
+    for (entity, transform, local_gravity, mut grounded) in &mut query {
+        let down = -local_gravity.up();
+        let position = transform.translation + CAPSULE_DEPTH / 2.0 * down;
+        let filter = QueryFilter::default()
+            .exclude_sensors()
+            .exclude_rigid_body(entity);
+
+        let value = rapier_context
+            .cast_shape(
+                position,
+                transform.rotation,
+                down,
+                &shape,
+                GROUND_DISTANCE + GROUND_CAST_MARGIN,
+                filter,
+            )
+            .is_some();
+
+        if grounded.value != value {
+            trace!("player grounded: {}", value);
+            grounded.value = value;
+        }
+
+        grounded.seconds_airborne = match value {
+            true => 0.0,
+            false => grounded.seconds_airborne + STEP_SECONDS,
+        };
+    }
+}
+
+/// A dying player's presses skip to their respawn rather than jumping.
+fn buffer_jumps(
+    mut hover_events: EventReader<HoverEvent>,
+    mut query: Query<&mut Jumping, Without<Dying>>,
+) {
+    for event in hover_events.iter() {
+        if let HoverEvent::Start = event {
+            for mut jumping in &mut query {
+                jumping.buffered_seconds = Some(0.0);
+            }
+        }
+    }
+}
+
+/*
+Jumping still needs fuel, as it did when every press of Space jumped, so that a level without
+fuel can't be climbed by jumping instead.
+*/
+fn jump(
+    mut query: Query<(
+        &mut Jumping,
+        &mut Grounded,
+        &Fuel,
+        &JumpImpulse,
+        &LocalGravity,
+        &mut Velocity,
+        &mut ExternalImpulse,
+    )>,
+) {
+    for (
+        mut jumping,
+        mut grounded,
+        fuel,
+        jump_impulse,
+        local_gravity,
+        mut velocity,
+        mut external_impulse,
+    ) in &mut query
+    {
+        jumping.cooldown_seconds = (jumping.cooldown_seconds - STEP_SECONDS).max(0.0);
+
+        let buffered_seconds = match jumping.buffered_seconds {
+            Some(buffered_seconds) => buffered_seconds,
+            None => continue,
+        };
+
+        let can_jump = grounded.seconds_airborne <= COYOTE_SECONDS
+            && jumping.cooldown_seconds <= 0.0
+            && fuel.available();
+
+        if can_jump {
+            trace!("player jumped");
+
+            let up = local_gravity.up();
+            let falling = velocity.linvel.dot(up).min(0.0);
+            velocity.linvel -= falling * up;
+            external_impulse.impulse += Quat::from_rotation_arc(Vec3::Y, up) * jump_impulse.value;
+
+            jumping.buffered_seconds = None;
+            jumping.cooldown_seconds = JUMP_COOLDOWN_SECONDS;
+            /* Coyote time is only for the first jump. */
+            grounded.seconds_airborne = COYOTE_SECONDS + STEP_SECONDS;
+        } else if buffered_seconds + STEP_SECONDS > JUMP_BUFFER_SECONDS {
+            jumping.buffered_seconds = None;
+        } else {
+            jumping.buffered_seconds = Some(buffered_seconds + STEP_SECONDS);
+        }
+    }
+}
+
+fn reset_jumping(
+    mut reset_events: EventReader<ResetEvent>,
+    mut query: Query<(&mut Jumping, &mut Grounded)>,
+) {
+    if reset_events.iter().last().is_some() {
+        for (mut jumping, mut grounded) in &mut query {
+            *jumping = Jumping::default();
+            *grounded = Grounded::default();
+        }
+    }
+}
+
+pub struct JumpPlugin;
+
+impl Plugin for JumpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                detect_ground,
+                buffer_jumps,
+                reset_jumping.after(buffer_jumps),
+                jump.after(detect_ground).after(reset_jumping),
+            )
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
+    }
+}
@@ -4,15 +4,32 @@ use bevy::{ecs::system::EntityCommands, prelude::*, reflect::TypeUuid};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    crumble::{Crumble, Crumbling},
+    fluid::{Fluid, FluidBundle},
     fuel_ball::FuelBallBundle,
-    wall::{WallBundle, WallType},
+    fuel_budget::FuelBudget,
+    gravity::{GravityZone, GravityZoneBundle},
+    light::{self, LightSettings, Spot},
+    rating::StarRating,
+    trigger::{Action, Trigger, TriggerBundle},
+    wall::{Appearance, WallBundle, WallType},
 };
 
 #[derive(Debug, Serialize, Deserialize, TypeUuid, Clone, Default)]
 #[uuid = "a79e94e4-1d11-4581-82f8-fb82cbc67f43"]
 pub struct Level {
     pub player_start: Vec3,
+    /// The player's initial facing, in radians around the `Y` axis. `0.0` faces `+Z`.
+    #[serde(default)]
+    pub player_start_yaw: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player_start_velocity: Option<Vec3>,
     pub initial_overlay: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "FuelBudget::is_default")]
+    pub fuel_budget: FuelBudget,
+    /// How completed runs are rated with stars. Levels without a rating aren't rated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stars: Option<StarRating>,
     pub structure: Vec<LevelItem>,
 }
 
@@ -23,38 +40,165 @@ pub enum LevelItem {
         position: Vec3,
         rotation: Quat,
         size: Vec2,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        appearance: Option<Appearance>,
+        /// Makes the wall fall away after the player touches it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        crumble: Option<Crumble>,
     },
     FuelBall {
         position: Vec3,
     },
     Light {
         position: Vec3,
-        intensity: f32,
+        #[serde(flatten)]
+        light: LightSettings,
+    },
+    SpotLight {
+        position: Vec3,
+        #[serde(flatten)]
+        spot: Spot,
+        #[serde(flatten)]
+        light: LightSettings,
+    },
+    /// An invisible volume that performs actions when the player enters, exits or stays inside it.
+    Trigger {
+        position: Vec3,
+        size: Vec3,
+        #[serde(flatten)]
+        trigger: Trigger,
+    },
+    /// A volume that replaces the world's gravity while the player is inside it.
+    GravityZone {
+        position: Vec3,
+        size: Vec3,
+        #[serde(flatten)]
+        gravity_zone: GravityZone,
+    },
+    /// A translucent volume of water that slows the player and pushes them up.
+    Fluid {
+        position: Vec3,
+        size: Vec3,
+        #[serde(flatten)]
+        fluid: Fluid,
     },
 }
 
+impl Level {
+    pub fn player_start_transform(&self) -> Transform {
+        Transform::from_translation(self.player_start)
+            .with_rotation(Quat::from_rotation_y(self.player_start_yaw))
+    }
+
+    pub fn player_start_velocity(&self) -> Vec3 {
+        self.player_start_velocity.unwrap_or(Vec3::ZERO)
+    }
+
+    /// The corners of the box around the player's start and the positions of every item.
+    pub fn bounds(&self) -> (Vec3, Vec3) {
+        self.structure.iter().map(LevelItem::position).fold(
+            (self.player_start, self.player_start),
+            |(min, max), position| (min.min(position), max.max(position)),
+        )
+    }
+
+    /**
+    Remove the level item at `index`.
+
+    Triggers refer to level items by index, so their references are updated to match, and actions
+    that targeted the removed item are dropped.
+    */
+    pub fn remove_item(&mut self, index: usize) -> LevelItem {
+        let removed = self.structure.remove(index);
+
+        for item in &mut self.structure {
+            if let LevelItem::Trigger { trigger, .. } = item {
+                trigger.retain_actions(|action| action.item() != Some(index));
+
+                for item_index in trigger.actions_mut().filter_map(Action::item_mut) {
+                    if *item_index > index {
+                        *item_index -= 1;
+                    }
+                }
+            }
+        }
+
+        removed
+    }
+}
+
 impl LevelItem {
+    pub fn position(&self) -> Vec3 {
+        match self {
+            LevelItem::Wall { position, .. } => *position,
+            LevelItem::FuelBall { position } => *position,
+            LevelItem::Light { position, .. } => *position,
+            LevelItem::SpotLight { position, .. } => *position,
+            LevelItem::Trigger { position, .. } => *position,
+            LevelItem::GravityZone { position, .. } => *position,
+            LevelItem::Fluid { position, .. } => *position,
+        }
+    }
+
     pub fn position_mut(&mut self) -> &mut Vec3 {
         match self {
             LevelItem::Wall { position, .. } => position,
             LevelItem::FuelBall { position } => position,
             LevelItem::Light { position, .. } => position,
+            LevelItem::SpotLight { position, .. } => position,
+            LevelItem::Trigger { position, .. } => position,
+            LevelItem::GravityZone { position, .. } => position,
+            LevelItem::Fluid { position, .. } => position,
         }
     }
 
-    pub fn size(&self) -> Option<Vec2> {
+    /// The size of the item's box, if it can be resized. Walls are flat, so they have no height.
+    pub fn size(&self) -> Option<Vec3> {
         match self {
-            LevelItem::Wall { size, .. } => Some(*size),
+            LevelItem::Wall { size, .. } => Some(Vec3::new(size.x, 0.0, size.y)),
             LevelItem::FuelBall { .. } => None,
             LevelItem::Light { .. } => None,
+            LevelItem::SpotLight { .. } => None,
+            LevelItem::Trigger { size, .. } => Some(*size),
+            LevelItem::GravityZone { size, .. } => Some(*size),
+            LevelItem::Fluid { size, .. } => Some(*size),
         }
     }
 
-    pub fn size_mut(&mut self) -> Option<&mut Vec2> {
+    /// Resize the item's box, if it can be resized. A wall's height is ignored.
+    pub fn set_size(&mut self, new_size: Vec3) {
         match self {
-            LevelItem::Wall { size, .. } => Some(size),
-            LevelItem::FuelBall { .. } => None,
-            LevelItem::Light { .. } => None,
+            LevelItem::Wall { size, .. } => *size = Vec2::new(new_size.x, new_size.z),
+            LevelItem::FuelBall { .. } => {}
+            LevelItem::Light { .. } => {}
+            LevelItem::SpotLight { .. } => {}
+            LevelItem::Trigger { size, .. } => *size = new_size,
+            LevelItem::GravityZone { size, .. } => *size = new_size,
+            LevelItem::Fluid { size, .. } => *size = new_size,
+        }
+    }
+
+    pub fn light(&self) -> Option<(&LightSettings, Option<&Spot>)> {
+        match self {
+            LevelItem::Light { light, .. } => Some((light, None)),
+            LevelItem::SpotLight { light, spot, .. } => Some((light, Some(spot))),
+            LevelItem::Wall { .. }
+            | LevelItem::FuelBall { .. }
+            | LevelItem::Trigger { .. }
+            | LevelItem::GravityZone { .. }
+            | LevelItem::Fluid { .. } => None,
+        }
+    }
+
+    pub fn light_mut(&mut self) -> Option<(&mut LightSettings, Option<&mut Spot>)> {
+        match self {
+            LevelItem::Light { light, .. } => Some((light, None)),
+            LevelItem::SpotLight { light, spot, .. } => Some((light, Some(spot))),
+            LevelItem::Wall { .. }
+            | LevelItem::FuelBall { .. }
+            | LevelItem::Trigger { .. }
+            | LevelItem::GravityZone { .. }
+            | LevelItem::Fluid { .. } => None,
         }
     }
 
@@ -63,6 +207,10 @@ impl LevelItem {
             LevelItem::Wall { rotation, .. } => Some(*rotation),
             LevelItem::FuelBall { .. } => None,
             LevelItem::Light { .. } => None,
+            LevelItem::SpotLight { .. } => None,
+            LevelItem::Trigger { .. } => None,
+            LevelItem::GravityZone { .. } => None,
+            LevelItem::Fluid { .. } => None,
         }
     }
 
@@ -78,53 +226,82 @@ impl LevelItem {
                 position,
                 rotation,
                 size,
-            } => match wall_type {
-                WallType::Neutral => commands.spawn(WallBundle::neutral(
-                    meshes, materials, *position, *rotation, *size,
-                )),
-                WallType::Avoid => commands.spawn(WallBundle::avoid(
-                    meshes, materials, *position, *rotation, *size,
-                )),
-                WallType::Goal => commands.spawn(WallBundle::goal(
-                    meshes, materials, *position, *rotation, *size,
-                )),
-            },
-            LevelItem::FuelBall { position } => {
-                commands.spawn(FuelBallBundle::new(meshes, materials, *position))
-            }
-            LevelItem::Light {
-                position,
-                intensity,
+                appearance,
+                crumble,
             } => {
-                let mut entity_commands = commands.spawn(PbrBundle {
-                    mesh: meshes.add(Mesh::from(shape::UVSphere {
-                        radius: 0.1,
-                        sectors: 20,
-                        stacks: 20,
-                    })),
-                    material: materials.add(StandardMaterial {
-                        base_color: Color::WHITE,
-                        emissive: Color::rgba_linear(100.0, 100.0, 100.0, 0.0),
-                        ..default()
-                    }),
-                    transform: Transform::from_translation(*position),
-                    ..default()
-                });
-
-                entity_commands.with_children(|parent| {
-                    parent.spawn(PointLightBundle {
-                        point_light: PointLight {
-                            intensity: *intensity,
-                            radius: 0.1,
-                            shadows_enabled: true,
-                            ..default()
-                        },
-                        ..default()
-                    });
-                });
+                let mut entity_commands = match wall_type {
+                    WallType::Neutral => commands.spawn(WallBundle::neutral(
+                        meshes,
+                        materials,
+                        *position,
+                        *rotation,
+                        *size,
+                        appearance.as_ref(),
+                    )),
+                    WallType::Avoid => commands.spawn(WallBundle::avoid(
+                        meshes,
+                        materials,
+                        *position,
+                        *rotation,
+                        *size,
+                        appearance.as_ref(),
+                    )),
+                    WallType::Goal => commands.spawn(WallBundle::goal(
+                        meshes,
+                        materials,
+                        *position,
+                        *rotation,
+                        *size,
+                        appearance.as_ref(),
+                    )),
+                };
+
+                if let Some(crumble) = crumble {
+                    entity_commands.insert(Crumbling::new(*crumble));
+                }
 
                 entity_commands
             }
+            LevelItem::FuelBall { position } => {
+                commands.spawn(FuelBallBundle::new(meshes, materials, *position))
+            }
+            LevelItem::Light { position, light } => {
+                light::spawn(commands, meshes, materials, *position, light, None)
+            }
+            LevelItem::SpotLight {
+                position,
+                spot,
+                light,
+            } => light::spawn(commands, meshes, materials, *position, light, Some(spot)),
+            LevelItem::Trigger {
+                position,
+                size,
+                trigger,
+            } => commands.spawn(TriggerBundle::new(
+                meshes,
+                materials,
+                *position,
+                *size,
+                trigger.clone(),
+            )),
+            LevelItem::GravityZone {
+                position,
+                size,
+                gravity_zone,
+            } => commands.spawn(GravityZoneBundle::new(
+                meshes,
+                materials,
+                *position,
+                *size,
+                *gravity_zone,
+            )),
+            LevelItem::Fluid {
+                position,
+                size,
+                fluid,
+            } => commands.spawn(FluidBundle::new(
+                meshes, materials, *position, *size, *fluid,
+            )),
         }
     }
 }
@@ -137,3 +314,112 @@ impl Plugin for LevelPlugin {
             .add_asset::<Level>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trigger::Stay;
+
+    fn fuel_ball(x: f32) -> LevelItem {
+        LevelItem::FuelBall {
+            position: Vec3::new(x, 0.0, 0.0),
+        }
+    }
+
+    fn targets(level: &Level) -> Vec<usize> {
+        level
+            .structure
+            .iter()
+            .filter_map(|item| match item {
+                LevelItem::Trigger { trigger, .. } => Some(trigger),
+                _ => None,
+            })
+            .flat_map(|trigger| trigger.actions().filter_map(Action::item))
+            .collect()
+    }
+
+    #[test]
+    fn remove_item_updates_trigger_targets() {
+        let trigger = Trigger {
+            on_enter: vec![
+                Action::SetVisible {
+                    item: 0,
+                    visible: false,
+                },
+                Action::Split,
+            ],
+            on_exit: vec![Action::SetCollider {
+                item: 1,
+                enabled: false,
+            }],
+            on_stay: Some(Stay {
+                seconds: 1.0,
+                actions: vec![Action::SetVisible {
+                    item: 2,
+                    visible: true,
+                }],
+            }),
+            ..default()
+        };
+
+        let mut level = Level {
+            structure: vec![
+                fuel_ball(0.0),
+                fuel_ball(1.0),
+                fuel_ball(2.0),
+                LevelItem::Trigger {
+                    position: Vec3::ZERO,
+                    size: Vec3::ONE,
+                    trigger,
+                },
+            ],
+            ..default()
+        };
+
+        let removed = level.remove_item(1);
+
+        assert_eq!(removed.position(), Vec3::new(1.0, 0.0, 0.0));
+        assert_eq!(level.structure.len(), 3);
+
+        /* Actions on the removed item are dropped, and later items move down by one. */
+        assert_eq!(targets(&level), vec![0, 1]);
+
+        /* Actions that don't target an item are kept. */
+        match &level.structure[2] {
+            LevelItem::Trigger { trigger, .. } => {
+                assert!(matches!(trigger.on_enter[..], [_, Action::Split]));
+                assert!(trigger.on_exit.is_empty());
+            }
+            _ => panic!("the trigger should have moved down to index 2"),
+        }
+    }
+
+    #[test]
+    fn walls_and_volumes_are_resized() {
+        let mut wall = LevelItem::Wall {
+            wall_type: WallType::Neutral,
+            position: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            size: Vec2::new(2.0, 3.0),
+            appearance: None,
+            crumble: None,
+        };
+        assert_eq!(wall.size(), Some(Vec3::new(2.0, 0.0, 3.0)));
+
+        /* Walls are flat, so their height is ignored. */
+        wall.set_size(Vec3::new(4.0, 5.0, 6.0));
+        assert_eq!(wall.size(), Some(Vec3::new(4.0, 0.0, 6.0)));
+
+        let mut trigger = LevelItem::Trigger {
+            position: Vec3::ZERO,
+            size: Vec3::ONE,
+            trigger: Trigger::default(),
+        };
+        trigger.set_size(Vec3::new(4.0, 5.0, 6.0));
+        assert_eq!(trigger.size(), Some(Vec3::new(4.0, 5.0, 6.0)));
+
+        let mut fuel_ball = fuel_ball(0.0);
+        fuel_ball.set_size(Vec3::ONE);
+        assert_eq!(fuel_ball.size(), None);
+    }
+}
@@ -10,14 +10,19 @@ use bevy_egui::{egui, EguiContexts};
 use bevy_rapier3d::prelude::{Collider, QueryFilter, RapierContext, RayIntersection, Real};
 
 use crate::{
+    actions::InputAction,
     camera::Zoom,
     colored_wireframe::ColoredWireframe,
     config::Config,
+    fluid::Fluid,
+    fuel::Fuel,
     level,
+    light::{self, LightSettings, Spot},
     load_level::{self, CurrentLevel, InCurrentLevel},
     main_menu, player,
+    settings::Settings,
     ui::{self, UI},
-    wall::{WallBundle, WallType},
+    wall::WallType,
     GameState,
 };
 
@@ -47,6 +52,9 @@ pub enum SpawnMode {
     Avoid,
     Neutral,
     Goal,
+    PointLight,
+    SpotLight,
+    Water,
 }
 
 pub struct StartEvent {
@@ -173,24 +181,14 @@ struct Rotate {
     rotating: bool,
 }
 
-fn handle_right_click(
-    mut mouse_button_events: EventReader<MouseButtonInput>,
-    mut rotate_query: Query<&mut Rotate>,
-) {
-    for event in mouse_button_events.iter() {
-        if let MouseButton::Right = event.button {
-            match event.state {
-                bevy::input::ButtonState::Pressed => {
-                    for mut rotate in &mut rotate_query {
-                        rotate.rotating = true;
-                    }
-                }
-                bevy::input::ButtonState::Released => {
-                    for mut rotate in &mut rotate_query {
-                        rotate.rotating = false;
-                    }
-                }
-            }
+fn handle_rotate(actions: Res<Input<InputAction>>, mut rotate_query: Query<&mut Rotate>) {
+    for mut rotate in &mut rotate_query {
+        if actions.just_pressed(InputAction::Rotate) {
+            rotate.rotating = true;
+        }
+
+        if actions.just_released(InputAction::Rotate) {
+            rotate.rotating = false;
         }
     }
 }
@@ -264,10 +262,7 @@ fn handle_object_hover(
             if let Some((entity, _position)) =
                 closest_intersection(rapier_context.as_ref(), transform.translation(), ray)
             {
-                if !matches!(
-                    highlight_query.get(entity),
-                    Ok((_, Highlight::Selected { .. }))
-                ) {
+                if !matches!(highlight_query.get(entity), Ok((_, Highlight::Selected))) {
                     debug!("hovered {:?}", entity);
 
                     commands
@@ -282,6 +277,7 @@ fn handle_object_hover(
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_drag(
     mut mouse_move_events: EventReader<MouseMotion>,
     cursor_moved_events: EventReader<CursorMoved>,
@@ -330,7 +326,7 @@ fn get_camera_ray(camera: &Camera, camera_global_transform: &GlobalTransform) ->
     let screen_position_ndc = Vec2::ZERO;
 
     let ndc_near = screen_position_ndc.extend(1.0);
-    let ndc_far = screen_position_ndc.extend(std::f32::EPSILON);
+    let ndc_far = screen_position_ndc.extend(f32::EPSILON);
 
     let ndc_to_world =
         camera_global_transform.compute_matrix() * camera.projection_matrix().inverse();
@@ -344,6 +340,7 @@ fn get_camera_ray(camera: &Camera, camera_global_transform: &GlobalTransform) ->
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_drag_object_action(
     camera_query: Query<(&Camera, &GlobalTransform)>,
     mut cursor_moved_events: EventReader<CursorMoved>,
@@ -495,6 +492,7 @@ fn handle_drag_object_action(
 }
 
 fn handle_drag_rotating(
+    settings: Res<Settings>,
     mut mouse_move_events: EventReader<MouseMotion>,
     mut query: Query<(&mut Transform, &Rotate)>,
 ) {
@@ -505,7 +503,7 @@ fn handle_drag_rotating(
 
         for (mut transform, rotate) in &mut query {
             if rotate.rotating {
-                let scale = 0.005;
+                let scale = settings.mouse_sensitivity;
                 transform.rotate_local_x(scale * -delta.y);
                 transform.rotate_y(scale * -delta.x);
             }
@@ -513,18 +511,19 @@ fn handle_drag_rotating(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_spawn(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    keycodes: Res<Input<KeyCode>>,
+    actions: Res<Input<InputAction>>,
     level_editor: ResMut<LevelEditor>,
     mut current_level: ResMut<CurrentLevel>,
     windows: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform)>,
     pan_query: Query<&Transform, With<Pan>>,
 ) {
-    if keycodes.just_pressed(KeyCode::Space) {
+    if actions.just_pressed(InputAction::SpawnItem) {
         let (camera, camera_global_transform) = camera_query.iter().next().unwrap();
         let pan_transform = pan_query.iter().next().unwrap();
 
@@ -542,64 +541,69 @@ fn handle_spawn(
         let rotation = Quat::default();
         let size = Vec2::new(5.0, 5.0);
 
-        let index = current_level.level.structure.len();
-
-        current_level.level.structure.push(level::LevelItem::Wall {
-            wall_type: match level_editor.spawn_mode {
-                SpawnMode::Avoid => WallType::Avoid,
-                SpawnMode::Neutral => WallType::Neutral,
-                SpawnMode::Goal => WallType::Goal,
-            },
+        let wall = |wall_type| level::LevelItem::Wall {
+            wall_type,
             position,
             rotation,
             size,
-        });
+            appearance: None,
+            crumble: None,
+        };
 
-        commands.spawn((
-            InCurrentLevel::LevelItem(index),
-            Size::new(size.x, size.y),
-            Rotation::ZERO,
-            match level_editor.spawn_mode {
-                SpawnMode::Avoid => {
-                    WallBundle::avoid(&mut meshes, &mut materials, position, rotation, size)
-                }
-                SpawnMode::Neutral => {
-                    WallBundle::neutral(&mut meshes, &mut materials, position, rotation, size)
-                }
-                SpawnMode::Goal => {
-                    WallBundle::goal(&mut meshes, &mut materials, position, rotation, size)
-                }
+        let level_item = match level_editor.spawn_mode {
+            SpawnMode::Avoid => wall(WallType::Avoid),
+            SpawnMode::Neutral => wall(WallType::Neutral),
+            SpawnMode::Goal => wall(WallType::Goal),
+            SpawnMode::PointLight => level::LevelItem::Light {
+                position,
+                light: LightSettings::default(),
+            },
+            SpawnMode::SpotLight => level::LevelItem::SpotLight {
+                position,
+                spot: Spot::default(),
+                light: LightSettings::default(),
+            },
+            SpawnMode::Water => level::LevelItem::Fluid {
+                position,
+                size: Vec3::new(size.x, size.y / 2.0, size.y),
+                fluid: Fluid::default(),
             },
-        ));
+        };
+
+        let index = current_level.level.structure.len();
+
+        let mut entity_commands = level_item.spawn(&mut commands, &mut meshes, &mut materials);
+        entity_commands.insert(InCurrentLevel::LevelItem(index));
+        annotate_level_item(&mut entity_commands, &level_item);
+
+        current_level.level.structure.push(level_item);
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn handle_delete(
     mut commands: Commands,
-    keycodes: Res<Input<KeyCode>>,
+    actions: Res<Input<InputAction>>,
     mut current_level: ResMut<CurrentLevel>,
     mut params: ParamSet<(
         Query<(Entity, &Highlight, &InCurrentLevel)>,
         Query<&mut InCurrentLevel>,
     )>,
 ) {
-    if keycodes.just_pressed(KeyCode::Delete) {
+    if actions.just_pressed(InputAction::DeleteItem) {
         debug!("delete pressed");
 
         let mut deleted_level_item_indices: Vec<usize> = Vec::new();
 
         let highlight_query = params.p0();
         for (entity, highlight, location) in &highlight_query {
-            if let Highlight::Selected { .. } = highlight {
+            if let Highlight::Selected = highlight {
                 match location {
                     InCurrentLevel::NoLocation => {}
                     InCurrentLevel::LevelItem(deleted_level_item_index) => {
                         deleted_level_item_indices.push(*deleted_level_item_index);
 
-                        current_level
-                            .level
-                            .structure
-                            .remove(*deleted_level_item_index);
+                        current_level.level.remove_item(*deleted_level_item_index);
 
                         commands.entity(entity).despawn_recursive();
                     }
@@ -642,6 +646,7 @@ fn handle_save_event(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn create_ui(
     level_editor_state: Res<State<LevelEditorState>>,
     mut level_editor: ResMut<LevelEditor>,
@@ -650,7 +655,13 @@ fn create_ui(
     mut save_event: EventWriter<SaveEvent>,
     mut test_event: EventWriter<TestEvent>,
     mut exit_event: EventWriter<ExitEvent>,
-    mut item_parameters_query: Query<(&Highlight, &mut Transform, &mut Size, &mut Rotation)>,
+    mut current_level: Option<ResMut<CurrentLevel>>,
+    mut item_parameters_query: Query<
+        (&Highlight, &mut Transform, &mut Size, Option<&mut Rotation>),
+        Without<PlayerToken>,
+    >,
+    mut player_token_query: Query<(&Highlight, &mut Transform), With<PlayerToken>>,
+    mut light_parameters_query: Query<(&Highlight, &mut LightParameters)>,
 ) {
     egui::Window::new("Level Editor")
         .fixed_pos((10.0, 10.0))
@@ -702,10 +713,20 @@ fn create_ui(
                     let _ =
                         ui.radio_value(&mut level_editor.spawn_mode, SpawnMode::Neutral, "neutral");
                     let _ = ui.radio_value(&mut level_editor.spawn_mode, SpawnMode::Goal, "goal");
+                    let _ = ui.radio_value(
+                        &mut level_editor.spawn_mode,
+                        SpawnMode::PointLight,
+                        "point light",
+                    );
+                    let _ = ui.radio_value(
+                        &mut level_editor.spawn_mode,
+                        SpawnMode::SpotLight,
+                        "spot light",
+                    );
+                    let _ = ui.radio_value(&mut level_editor.spawn_mode, SpawnMode::Water, "water");
                 });
 
-                for (highlight, mut transform, mut size, mut rotation) in &mut item_parameters_query
-                {
+                for (highlight, mut transform, mut size, rotation) in &mut item_parameters_query {
                     if let Highlight::Selected = highlight {
                         ui.add_space(10.0);
 
@@ -742,7 +763,7 @@ fn create_ui(
                                 ui.horizontal(|ui| {
                                     ui.label("length");
                                     let _ = ui.add(
-                                        egui::DragValue::new(&mut size.y)
+                                        egui::DragValue::new(&mut size.value.z)
                                             .speed(1.0)
                                             .clamp_range(1.0..=f32::INFINITY),
                                     );
@@ -751,30 +772,100 @@ fn create_ui(
                                 ui.horizontal(|ui| {
                                     ui.label("width");
                                     let _ = ui.add(
-                                        egui::DragValue::new(&mut size.x)
+                                        egui::DragValue::new(&mut size.value.x)
                                             .speed(1.0)
                                             .clamp_range(1.0..=f32::INFINITY),
                                     );
                                 });
+
+                                if size.has_height() {
+                                    ui.horizontal(|ui| {
+                                        ui.label("height");
+                                        let _ = ui.add(
+                                            egui::DragValue::new(&mut size.value.y)
+                                                .speed(1.0)
+                                                .clamp_range(1.0..=f32::INFINITY),
+                                        );
+                                    });
+                                }
                             });
 
-                            ui.vertical(|ui| {
-                                ui.horizontal(|ui| {
-                                    ui.label("x");
-                                    let _ = ui.add(egui::Slider::new(&mut rotation.x, 0.0..=360.0));
+                            if let Some(mut rotation) = rotation {
+                                ui.vertical(|ui| {
+                                    ui.horizontal(|ui| {
+                                        ui.label("x");
+                                        let _ =
+                                            ui.add(egui::Slider::new(&mut rotation.x, 0.0..=360.0));
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        ui.label("y");
+                                        let _ =
+                                            ui.add(egui::Slider::new(&mut rotation.y, 0.0..=360.0));
+                                    });
+
+                                    ui.horizontal(|ui| {
+                                        ui.label("z");
+                                        let _ =
+                                            ui.add(egui::Slider::new(&mut rotation.z, 0.0..=360.0));
+                                    });
                                 });
+                            }
+                        });
+                    }
+                }
 
-                                ui.horizontal(|ui| {
-                                    ui.label("y");
-                                    let _ = ui.add(egui::Slider::new(&mut rotation.y, 0.0..=360.0));
-                                });
+                for (highlight, mut parameters) in &mut light_parameters_query {
+                    if let Highlight::Selected = highlight {
+                        ui.add_space(10.0);
 
-                                ui.horizontal(|ui| {
-                                    ui.label("z");
-                                    let _ = ui.add(egui::Slider::new(&mut rotation.z, 0.0..=360.0));
-                                });
-                            });
+                        ui.heading("Light");
+
+                        light_parameters_ui(ui, &mut parameters);
+                    }
+                }
+
+                for (highlight, mut transform) in &mut player_token_query {
+                    if let Highlight::Selected = highlight {
+                        ui.add_space(10.0);
+
+                        ui.heading("Player Start");
+
+                        ui.horizontal(|ui| {
+                            ui.label("facing");
+
+                            let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+                            let mut degrees = yaw.to_degrees();
+                            if ui
+                                .add(egui::Slider::new(&mut degrees, -180.0..=180.0))
+                                .changed()
+                            {
+                                transform.rotation = Quat::from_rotation_y(degrees.to_radians());
+                            }
                         });
+
+                        if let Some(current_level) = current_level.as_mut() {
+                            ui.horizontal(|ui| {
+                                ui.label("velocity");
+
+                                let mut velocity = current_level.level.player_start_velocity();
+                                let mut changed = false;
+                                for (label, value) in [
+                                    ("x", &mut velocity.x),
+                                    ("y", &mut velocity.y),
+                                    ("z", &mut velocity.z),
+                                ] {
+                                    ui.label(label);
+                                    changed |=
+                                        ui.add(egui::DragValue::new(value).speed(0.1)).changed();
+                                }
+
+                                if changed {
+                                    current_level.level.player_start_velocity =
+                                        (velocity != Vec3::ZERO).then_some(velocity);
+                                }
+                            });
+                        }
                     }
                 }
 
@@ -792,7 +883,7 @@ fn create_ui(
                     })
                 });
             }
-            LevelEditorState::Testing { .. } => {
+            LevelEditorState::Testing => {
                 ui.vertical_centered(|ui| {
                     if ui.button("stop testing").clicked() {
                         test_event.send(TestEvent::Stop);
@@ -805,11 +896,86 @@ fn create_ui(
         });
 }
 
+fn light_parameters_ui(ui: &mut egui::Ui, parameters: &mut LightParameters) {
+    let LightParameters { light, spot } = parameters;
+
+    ui.horizontal(|ui| {
+        ui.label("intensity");
+        let _ = ui.add(
+            egui::DragValue::new(&mut light.intensity)
+                .speed(10.0)
+                .clamp_range(0.0..=f32::INFINITY),
+        );
+
+        ui.label("colour");
+        let [red, green, blue, _] = light.color.as_rgba_f32();
+        let mut color = [red, green, blue];
+        if ui.color_edit_button_rgb(&mut color).changed() {
+            light.color = Color::rgb(color[0], color[1], color[2]);
+        }
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("range");
+        let _ = ui.add(
+            egui::DragValue::new(&mut light.range)
+                .speed(0.1)
+                .clamp_range(0.0..=f32::INFINITY),
+        );
+
+        ui.label("radius");
+        let _ = ui.add(
+            egui::DragValue::new(&mut light.radius)
+                .speed(0.01)
+                .clamp_range(0.0..=f32::INFINITY),
+        );
+
+        let _ = ui.checkbox(&mut light.shadows_enabled, "shadows");
+    });
+
+    if let Some(spot) = spot {
+        ui.horizontal(|ui| {
+            ui.label("direction");
+            for (label, value) in [
+                ("x", &mut spot.direction.x),
+                ("y", &mut spot.direction.y),
+                ("z", &mut spot.direction.z),
+            ] {
+                ui.label(label);
+                let _ = ui.add(egui::DragValue::new(value).speed(0.05));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let mut outer_degrees = spot.outer_angle.to_degrees();
+            let mut inner_degrees = spot.inner_angle.to_degrees();
+
+            ui.label("inner angle");
+            if ui
+                .add(egui::Slider::new(&mut inner_degrees, 0.0..=outer_degrees))
+                .changed()
+            {
+                spot.inner_angle = inner_degrees.to_radians();
+            }
+
+            ui.label("outer angle");
+            if ui
+                .add(egui::Slider::new(&mut outer_degrees, 0.0..=90.0))
+                .changed()
+            {
+                spot.outer_angle = outer_degrees.to_radians();
+                spot.inner_angle = spot.inner_angle.min(spot.outer_angle);
+            }
+        });
+    }
+}
+
 enum TestEvent {
     Start,
     Stop,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn handle_test_event(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -833,7 +999,12 @@ fn handle_test_event(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    Transform::from_translation(current_level.level.player_start),
+                    current_level.level.player_start_transform(),
+                    current_level.level.player_start_velocity(),
+                    Fuel::new(
+                        current_level.level.fuel_budget.starting_fuel,
+                        current_level.level.fuel_budget.model,
+                    ),
                     None,
                 )
                 .insert(InCurrentLevel::NoLocation);
@@ -849,7 +1020,7 @@ fn handle_test_event(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    current_level.level.player_start,
+                    current_level.level.player_start_transform(),
                 );
 
                 level_editor_state.set(LevelEditorState::Editing);
@@ -865,9 +1036,9 @@ pub fn spawn_player_token<'w, 's, 'a>(
     commands: &'a mut Commands<'w, 's>,
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
-    position: Vec3,
+    transform: Transform,
 ) -> EntityCommands<'w, 's, 'a> {
-    commands.spawn((
+    let mut entity_commands = commands.spawn((
         PlayerToken,
         PbrBundle {
             mesh: meshes.add(Mesh::from(shape::Capsule {
@@ -876,20 +1047,40 @@ pub fn spawn_player_token<'w, 's, 'a>(
                 ..default()
             })),
             material: materials.add(player::CAPSULE_COLOR.into()),
-            transform: Transform::from_translation(position),
+            transform,
             ..default()
         },
         Collider::capsule_y(player::CAPSULE_DEPTH / 2.0, player::CAPSULE_RADIUS),
         InCurrentLevel::NoLocation,
-    ))
+    ));
+
+    // Marks the direction that the player will face.
+    entity_commands.with_children(|parent| {
+        parent.spawn(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 0.2 })),
+            material: materials.add(Color::BLACK.into()),
+            transform: Transform::from_xyz(
+                0.0,
+                player::CAPSULE_DEPTH / 2.0,
+                player::CAPSULE_RADIUS,
+            ),
+            ..default()
+        });
+    });
+
+    entity_commands
 }
 
+#[allow(clippy::type_complexity)]
 fn move_player_start(
     mut current_level: ResMut<CurrentLevel>,
     query: Query<&Transform, (Changed<Transform>, With<PlayerToken>, With<InCurrentLevel>)>,
 ) {
     for transform in query.iter() {
+        let (yaw, _, _) = transform.rotation.to_euler(EulerRot::YXZ);
+
         current_level.level.player_start = transform.translation;
+        current_level.level.player_start_yaw = yaw;
     }
 }
 
@@ -916,21 +1107,23 @@ fn move_level_item(
 
 #[derive(Component)]
 struct Size {
-    x: f32,
-    y: f32,
-    x_unscaled: f32,
-    y_unscaled: f32,
+    value: Vec3,
+    /// The size the item was spawned with, which its mesh and collider are built for.
+    unscaled: Vec3,
 }
 
 impl Size {
-    fn new(x: f32, y: f32) -> Self {
+    fn new(size: Vec3) -> Self {
         Size {
-            x,
-            y,
-            x_unscaled: x,
-            y_unscaled: y,
+            value: size,
+            unscaled: size,
         }
     }
+
+    /// Walls are flat, so only volumes can be made taller.
+    fn has_height(&self) -> bool {
+        self.unscaled.y > 0.0
+    }
 }
 
 fn scale_level_item(
@@ -941,17 +1134,16 @@ fn scale_level_item(
         match location {
             InCurrentLevel::NoLocation => {}
             InCurrentLevel::LevelItem(level_item_index) => {
-                if let Some(level_item_size) =
-                    current_level.level.structure[*level_item_index].size_mut()
-                {
-                    level_item_size.x = size.x;
-                    level_item_size.y = size.y;
-                }
+                current_level.level.structure[*level_item_index].set_size(size.value);
 
                 transform.scale = Vec3 {
-                    x: size.x / size.x_unscaled,
-                    y: transform.scale.y,
-                    z: size.y / size.y_unscaled,
+                    x: size.value.x / size.unscaled.x,
+                    y: if size.has_height() {
+                        size.value.y / size.unscaled.y
+                    } else {
+                        transform.scale.y
+                    },
+                    z: size.value.z / size.unscaled.z,
                 };
             }
         }
@@ -966,12 +1158,6 @@ struct Rotation {
 }
 
 impl Rotation {
-    const ZERO: Self = Rotation {
-        x: 0.0,
-        y: 0.0,
-        z: 0.0,
-    };
-
     fn from_quat(quat: &Quat) -> Self {
         use std::f32::consts::TAU;
 
@@ -995,6 +1181,63 @@ impl Rotation {
     }
 }
 
+#[derive(Component)]
+struct LightParameters {
+    light: LightSettings,
+    spot: Option<Spot>,
+}
+
+fn update_light_item(
+    mut current_level: ResMut<CurrentLevel>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<
+        (
+            &InCurrentLevel,
+            &LightParameters,
+            &Handle<StandardMaterial>,
+            &Children,
+        ),
+        Changed<LightParameters>,
+    >,
+    mut point_light_query: Query<&mut PointLight>,
+    mut spot_light_query: Query<(&mut SpotLight, &mut Transform)>,
+    mut casts_shadows_query: Query<&mut light::CastsShadows>,
+) {
+    for (location, parameters, material, children) in &query {
+        if let InCurrentLevel::LevelItem(level_item_index) = location {
+            if let Some((light, spot)) =
+                current_level.level.structure[*level_item_index].light_mut()
+            {
+                *light = parameters.light;
+                if let (Some(spot), Some(new_spot)) = (spot, parameters.spot) {
+                    *spot = new_spot;
+                }
+            }
+        }
+
+        if let Some(material) = materials.get_mut(material) {
+            *material = light::bulb_material(&parameters.light);
+        }
+
+        for child in children.iter() {
+            if let Ok(mut point_light) = point_light_query.get_mut(*child) {
+                *point_light = light::point_light(&parameters.light);
+            }
+
+            if let (Ok((mut spot_light, mut transform)), Some(spot)) =
+                (spot_light_query.get_mut(*child), &parameters.spot)
+            {
+                *spot_light = light::spot_light(&parameters.light, spot);
+                *transform = spot.transform();
+            }
+
+            if let Ok(mut casts_shadows) = casts_shadows_query.get_mut(*child) {
+                casts_shadows.0 = parameters.light.shadows_enabled;
+            }
+        }
+    }
+}
+
 fn rotate_level_item(mut query: Query<(&mut Transform, &Rotation), Changed<Rotation>>) {
     for (mut transform, rotation) in &mut query {
         transform.rotation = rotation.to_quat();
@@ -1011,20 +1254,30 @@ fn annotate_level_items(
             InCurrentLevel::NoLocation => {}
             InCurrentLevel::LevelItem(level_item_index) => {
                 let level_item = &current_level.level.structure[*level_item_index];
-                let mut entity_commands = commands.entity(entity);
-
-                if let Some(size) = level_item.size() {
-                    entity_commands.insert(Size::new(size.x, size.y));
-                }
-
-                if let Some(rotation) = level_item.rotation() {
-                    entity_commands.insert(Rotation::from_quat(&rotation));
-                }
+                annotate_level_item(&mut commands.entity(entity), level_item);
             }
         }
     }
 }
 
+/// Add the components that the editor uses to modify a level item.
+fn annotate_level_item(entity_commands: &mut EntityCommands, level_item: &level::LevelItem) {
+    if let Some(size) = level_item.size() {
+        entity_commands.insert(Size::new(size));
+    }
+
+    if let Some(rotation) = level_item.rotation() {
+        entity_commands.insert(Rotation::from_quat(&rotation));
+    }
+
+    if let Some((light, spot)) = level_item.light() {
+        entity_commands.insert(LightParameters {
+            light: *light,
+            spot: spot.copied(),
+        });
+    }
+}
+
 #[derive(Resource)]
 struct LevelEditorCamera {
     entity: Entity,
@@ -1050,11 +1303,6 @@ fn setup_level_editor_camera(mut commands: Commands) {
             and allow the parent transform to control orientation.
             */
             transform: Transform::from_xyz(0.0, 0.0, 40.0).looking_at(Vec3::ZERO, Vec3::Y),
-            projection: PerspectiveProjection {
-                fov: (60.0 / 360.0) * 2.0 * std::f32::consts::PI,
-                ..default()
-            }
-            .into(),
             ..Default::default()
         })
         .insert(Zoom)
@@ -1194,6 +1442,7 @@ impl Plugin for LevelEditorPlugin {
                     move_level_item,
                     scale_level_item,
                     rotate_level_item,
+                    update_light_item,
                 )
                     .in_set(LevelEditorSet::Transform),
             );
@@ -1217,7 +1466,7 @@ impl Plugin for LevelEditorPlugin {
             (
                 // interactions
                 handle_left_click,
-                handle_right_click,
+                handle_rotate,
                 handle_object_hover,
                 handle_drag,
                 handle_drag_rotating,
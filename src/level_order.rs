@@ -1,39 +1,141 @@
-use bevy::{prelude::*, reflect::TypeUuid};
+use bevy::{asset::LoadState, prelude::*, reflect::TypeUuid};
 use serde::{Deserialize, Serialize};
 
+use crate::config::Config;
+
 pub mod asset;
 
+/// A campaign: a named sequence of chapters, each of which is a sequence of levels.
 #[derive(Serialize, Deserialize, TypeUuid, Clone, Default)]
 #[uuid = "330a713c-2dff-422f-a9a4-b59ef1239eab"]
-pub struct LevelOrder(Vec<String>);
+pub struct LevelOrder {
+    pub name: String,
+    pub chapters: Vec<Chapter>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Chapter {
+    pub name: String,
+    #[serde(default)]
+    pub unlock: Unlock,
+    pub levels: Vec<LevelEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LevelEntry {
+    pub path: String,
+    pub name: String,
+}
+
+/// The requirement that must be met before a chapter's levels can be played.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub enum Unlock {
+    #[default]
+    Always,
+    /// Complete at least `count` levels of the previous chapter.
+    CompletePrevious { count: usize },
+    /// Complete every level of the previous chapter.
+    CompleteAllPrevious,
+}
 
 impl LevelOrder {
+    pub fn levels(&self) -> impl Iterator<Item = &LevelEntry> {
+        self.chapters
+            .iter()
+            .flat_map(|chapter| chapter.levels.iter())
+    }
+
+    pub fn contains(&self, level_path: &str) -> bool {
+        self.levels().any(|level| level.path == level_path)
+    }
+
     pub fn next_level<'a>(&'a self, current_level_name: &str) -> Option<&'a str> {
-        let current_level_index = self.0.iter().enumerate().find_map(|(index, level_name)| {
-            if level_name == current_level_name {
-                Some(index)
-            } else {
-                None
-            }
-        });
+        let mut levels = self.levels();
+
+        levels
+            .find(|level| level.path == current_level_name)
+            .and_then(|_| levels.next())
+            .map(|level| level.path.as_ref())
+    }
+
+    /**
+    Whether the chapter at `chapter_index` can be played.
+
+    A chapter is only unlocked once every chapter before it is, so a requirement on the previous
+    chapter can't be met by completing levels of a chapter the player hasn't reached.
+
+    `is_complete` reports whether the player has completed the level at the given path.
+    */
+    pub fn is_chapter_unlocked(
+        &self,
+        chapter_index: usize,
+        is_complete: impl Fn(&str) -> bool,
+    ) -> bool {
+        chapter_index < self.chapters.len()
+            && (0..=chapter_index).all(|index| self.meets_unlock(index, &is_complete))
+    }
+
+    /// Whether the previous chapter meets the chapter at `chapter_index`'s own requirement.
+    fn meets_unlock(&self, chapter_index: usize, is_complete: &impl Fn(&str) -> bool) -> bool {
+        let previous = match chapter_index.checked_sub(1) {
+            Some(previous_index) => &self.chapters[previous_index],
+            None => return true,
+        };
 
-        current_level_index
-            .and_then(|index| self.0.get(index + 1))
-            .map(|level_name| level_name.as_ref())
+        let completed = previous
+            .levels
+            .iter()
+            .filter(|level| is_complete(&level.path))
+            .count();
+
+        match self.chapters[chapter_index].unlock {
+            Unlock::Always => true,
+            Unlock::CompletePrevious { count } => completed >= count,
+            Unlock::CompleteAllPrevious => completed == previous.levels.len(),
+        }
+    }
+
+    /// The index of the chapter that the level at `level_path` is in.
+    pub fn chapter_index(&self, level_path: &str) -> Option<usize> {
+        self.chapters
+            .iter()
+            .position(|chapter| chapter.levels.iter().any(|level| level.path == level_path))
+    }
+
+    pub fn is_level_unlocked(&self, level_path: &str, is_complete: impl Fn(&str) -> bool) -> bool {
+        self.chapter_index(level_path)
+            .is_some_and(|chapter_index| self.is_chapter_unlocked(chapter_index, is_complete))
+    }
+
+    pub fn level_name<'a>(&'a self, level_path: &str) -> Option<&'a str> {
+        self.levels()
+            .find(|level| level.path == level_path)
+            .map(|level| level.name.as_ref())
     }
 }
 
 #[derive(Resource)]
-pub struct LoadingLevelOrder {
-    pub handle: Handle<LevelOrder>,
+pub struct LoadingLevelOrders {
+    pub handles: Vec<Handle<LevelOrder>>,
 }
 
-fn start_loading(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let path = "default.level_order.json";
-    trace!("start_loading: {:?}", path);
+fn start_loading(mut commands: Commands, asset_server: Res<AssetServer>, config: Res<Config>) {
+    let handles = config
+        .level_orders
+        .iter()
+        .map(|path| {
+            trace!("start_loading: {:?}", path);
+            asset_server.load(path.as_str())
+        })
+        .collect();
+
+    commands.insert_resource(LoadingLevelOrders { handles });
+}
 
-    let handle = asset_server.load(path);
-    commands.insert_resource(LoadingLevelOrder { handle });
+/// Every campaign that loaded successfully, in the order they're listed in [`Config`].
+#[derive(Resource, Default)]
+pub struct LevelOrders {
+    pub campaigns: Vec<(Handle<LevelOrder>, LevelOrder)>,
 }
 
 #[derive(Resource)]
@@ -42,18 +144,69 @@ pub struct CurrentLevelOrder {
     pub level_order: LevelOrder,
 }
 
+pub struct SelectLevelOrderEvent {
+    pub handle: Handle<LevelOrder>,
+}
+
 fn finish_loading(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     assets: Res<Assets<LevelOrder>>,
-    loading_level_order: Res<LoadingLevelOrder>,
+    loading_level_orders: Res<LoadingLevelOrders>,
 ) {
-    if let Some(level_order) = assets.get(&loading_level_order.handle) {
+    let finished = loading_level_orders.handles.iter().all(|handle| {
+        matches!(
+            asset_server.get_load_state(handle),
+            LoadState::Loaded | LoadState::Failed
+        )
+    });
+
+    if finished {
         trace!("finish_loading");
-        commands.remove_resource::<LoadingLevelOrder>();
-        commands.insert_resource(CurrentLevelOrder {
-            handle: loading_level_order.handle.clone(),
-            level_order: level_order.clone(),
-        });
+        commands.remove_resource::<LoadingLevelOrders>();
+
+        let campaigns: Vec<(Handle<LevelOrder>, LevelOrder)> = loading_level_orders
+            .handles
+            .iter()
+            .filter_map(|handle| {
+                assets
+                    .get(handle)
+                    .map(|level_order| (handle.clone(), level_order.clone()))
+            })
+            .collect();
+
+        match campaigns.first() {
+            Some((handle, level_order)) => {
+                commands.insert_resource(CurrentLevelOrder {
+                    handle: handle.clone(),
+                    level_order: level_order.clone(),
+                });
+            }
+            None => {
+                error!("no level orders loaded");
+            }
+        }
+
+        commands.insert_resource(LevelOrders { campaigns });
+    }
+}
+
+fn handle_select_level_order(
+    mut commands: Commands,
+    level_orders: Res<LevelOrders>,
+    mut select_events: EventReader<SelectLevelOrderEvent>,
+) {
+    if let Some(SelectLevelOrderEvent { handle }) = select_events.iter().last() {
+        if let Some((handle, level_order)) = level_orders
+            .campaigns
+            .iter()
+            .find(|(campaign_handle, _)| campaign_handle == handle)
+        {
+            commands.insert_resource(CurrentLevelOrder {
+                handle: handle.clone(),
+                level_order: level_order.clone(),
+            });
+        }
     }
 }
 
@@ -62,14 +215,21 @@ fn hotreload(
     assets: Res<Assets<LevelOrder>>,
     mut asset_event: EventReader<AssetEvent<LevelOrder>>,
     current_level_order: Res<CurrentLevelOrder>,
+    mut level_orders: ResMut<LevelOrders>,
 ) {
     for event in asset_event.iter() {
         if let AssetEvent::Modified {
             handle: modified_handle,
         } = event
         {
-            if modified_handle == &current_level_order.handle {
-                if let Some(level_order) = assets.get(&current_level_order.handle) {
+            if let Some(level_order) = assets.get(modified_handle) {
+                for (handle, campaign) in &mut level_orders.campaigns {
+                    if handle == modified_handle {
+                        *campaign = level_order.clone();
+                    }
+                }
+
+                if modified_handle == &current_level_order.handle {
                     commands.insert_resource(CurrentLevelOrder {
                         handle: current_level_order.handle.clone(),
                         level_order: level_order.clone(),
@@ -86,8 +246,105 @@ impl Plugin for LevelOrderPlugin {
     fn build(&self, app: &mut App) {
         app.init_asset_loader::<asset::LevelOrderAssetLoader>()
             .add_asset::<LevelOrder>()
+            .add_event::<SelectLevelOrderEvent>()
             .add_startup_system(start_loading)
-            .add_system(finish_loading.run_if(resource_exists::<LoadingLevelOrder>()))
-            .add_system(hotreload.run_if(resource_exists::<CurrentLevelOrder>()));
+            .add_system(finish_loading.run_if(resource_exists::<LoadingLevelOrders>()))
+            .add_system(handle_select_level_order.run_if(resource_exists::<LevelOrders>()))
+            .add_system(
+                hotreload
+                    .run_if(resource_exists::<CurrentLevelOrder>())
+                    .run_if(resource_exists::<LevelOrders>()),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chapter(name: &str, unlock: Unlock, paths: &[&str]) -> Chapter {
+        Chapter {
+            name: name.to_string(),
+            unlock,
+            levels: paths
+                .iter()
+                .map(|path| LevelEntry {
+                    path: path.to_string(),
+                    name: path.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn level_order() -> LevelOrder {
+        LevelOrder {
+            name: "campaign".to_string(),
+            chapters: vec![
+                chapter("one", Unlock::Always, &["a", "b", "c"]),
+                chapter("two", Unlock::CompletePrevious { count: 2 }, &["d", "e"]),
+                chapter("three", Unlock::CompleteAllPrevious, &["f"]),
+            ],
+        }
+    }
+
+    #[test]
+    fn next_level_continues_into_the_next_chapter() {
+        let level_order = level_order();
+
+        assert_eq!(level_order.next_level("a"), Some("b"));
+        assert_eq!(level_order.next_level("c"), Some("d"));
+        assert_eq!(level_order.next_level("f"), None);
+        assert_eq!(level_order.next_level("missing"), None);
+    }
+
+    #[test]
+    fn first_chapter_is_always_unlocked() {
+        assert!(level_order().is_chapter_unlocked(0, |_| false));
+    }
+
+    #[test]
+    fn complete_previous_counts_the_previous_chapter() {
+        let level_order = level_order();
+
+        assert!(!level_order.is_chapter_unlocked(1, |path| path == "a"));
+        assert!(level_order.is_chapter_unlocked(1, |path| path == "a" || path == "c"));
+
+        /* The chapter's own levels don't count towards unlocking it. */
+        assert!(!level_order.is_chapter_unlocked(1, |path| path == "a" || path == "d"));
+    }
+
+    #[test]
+    fn complete_all_previous_needs_every_level_of_the_previous_chapter() {
+        let level_order = level_order();
+
+        assert!(!level_order.is_chapter_unlocked(2, |path| ["a", "b", "d"].contains(&path)));
+        assert!(level_order.is_chapter_unlocked(2, |path| ["a", "b", "d", "e"].contains(&path)));
+
+        /* Completing every earlier level doesn't make up for the previous chapter. */
+        assert!(!level_order.is_chapter_unlocked(2, |path| ["a", "b", "c", "d"].contains(&path)));
+    }
+
+    #[test]
+    fn chapters_are_locked_behind_a_locked_previous_chapter() {
+        let level_order = level_order();
+
+        /* Chapter two is locked, so completing its levels can't unlock chapter three. */
+        assert!(!level_order.is_chapter_unlocked(2, |path| path == "d" || path == "e"));
+        assert!(!level_order.is_level_unlocked("f", |path| path == "d" || path == "e"));
+    }
+
+    #[test]
+    fn chapters_that_dont_exist_are_locked() {
+        assert!(!level_order().is_chapter_unlocked(3, |_| true));
+    }
+
+    #[test]
+    fn levels_are_unlocked_with_their_chapter() {
+        let level_order = level_order();
+
+        assert!(level_order.is_level_unlocked("b", |_| false));
+        assert!(!level_order.is_level_unlocked("d", |_| false));
+        assert!(level_order.is_level_unlocked("d", |path| path == "b" || path == "c"));
+        assert!(!level_order.is_level_unlocked("missing", |_| true));
     }
 }
@@ -12,8 +12,20 @@ impl AssetLoader for LevelOrderAssetLoader {
         load_context: &'a mut bevy::asset::LoadContext,
     ) -> bevy::utils::BoxedFuture<'a, Result<(), bevy::asset::Error>> {
         Box::pin(async move {
-            let level = serde_json::from_slice::<LevelOrder>(bytes)?;
-            load_context.set_default_asset(LoadedAsset::new(level));
+            let level_order = serde_json::from_slice::<LevelOrder>(bytes)?;
+
+            for level in level_order.levels() {
+                if let Err(err) = load_context.read_asset_bytes(&level.path).await {
+                    return Err(bevy::asset::Error::msg(format!(
+                        "level order {:?} references missing level {:?}: {}",
+                        load_context.path(),
+                        level.path,
+                        err
+                    )));
+                }
+            }
+
+            load_context.set_default_asset(LoadedAsset::new(level_order));
             Ok(())
         })
     }
@@ -1,24 +1,46 @@
+pub mod actions;
 pub mod camera;
 pub mod collision;
 pub mod colored_wireframe;
 pub mod config;
 pub mod r#continue;
 pub mod controls;
+pub mod crumble;
 pub mod cylinder;
 pub mod debug;
+#[cfg(feature = "embedded-assets")]
+pub mod embedded_assets;
+pub mod fluid;
 pub mod fuel;
 pub mod fuel_ball;
+pub mod fuel_budget;
+pub mod ghost;
+pub mod gravity;
 pub mod hover;
 pub mod jump;
 pub mod level;
 pub mod level_editor;
 pub mod level_order;
+pub mod light;
 pub mod load_level;
 pub mod main_menu;
 pub mod next_level;
 pub mod pause;
+pub mod pause_menu;
 pub mod player;
+pub mod profile;
+pub mod rating;
+pub mod records;
+pub mod replay;
 pub mod reset;
+pub mod respawn;
+pub mod retry;
+pub mod save;
+pub mod settings;
+pub mod simulation;
+pub mod stats;
+pub mod timer;
+pub mod trigger;
 pub mod ui;
 pub mod wall;
 
@@ -28,7 +50,6 @@ use bevy::{
 };
 use bevy_atmosphere::prelude::AtmospherePlugin;
 use bevy_egui::EguiPlugin;
-use bevy_rapier3d::prelude::{NoUserData, RapierPhysicsPlugin};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, States)]
 pub enum GameState {
@@ -51,54 +72,89 @@ impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         let config = config::Config {
             asset_dir: "assets".into(),
+            level_orders: vec!["default.level_order.json".into()],
         };
 
+        let asset_plugin = AssetPlugin {
+            watch_for_changes: !cfg!(feature = "embedded-assets"),
+            asset_folder: config.asset_dir.clone(),
+        };
+
+        #[cfg(feature = "embedded-assets")]
+        {
+            // `AssetPlugin` only creates its own `AssetServer` if there isn't one already.
+            let overrides = if cfg!(target_family = "wasm") {
+                None
+            } else {
+                Some(asset_plugin.create_platform_default_asset_io())
+            };
+
+            app.insert_resource(AssetServer::with_boxed_io(Box::new(
+                embedded_assets::EmbeddedAssetIo::new(overrides),
+            )));
+        }
+
         app.edit_schedule(CoreSchedule::Main, |schedule| {
             schedule.set_build_settings(ScheduleBuildSettings {
                 ambiguity_detection: LogLevel::Warn,
                 ..default()
             });
         })
-        .add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    watch_for_changes: true,
-                    asset_folder: config.asset_dir.clone(),
-                })
-                .set(WindowPlugin {
-                    primary_window: Some(Window {
-                        title: "Hover Pill".to_string(),
-                        canvas: Some("#app".to_string()),
-                        ..default()
-                    }),
-                    ..default()
-                }),
-        )
+        .add_plugins(DefaultPlugins.set(asset_plugin).set(WindowPlugin {
+            primary_window: Some(Window {
+                title: "Hover Pill".to_string(),
+                canvas: Some("#app".to_string()),
+                ..default()
+            }),
+            ..default()
+        }))
         .add_state::<GameState>()
         .insert_resource(config)
         .insert_resource(colored_wireframe::ColoredWireframeConfig { enabled: true })
         .add_plugin(colored_wireframe::ColoredWireframePlugin)
         .add_plugin(EguiPlugin)
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::default())
+        .add_plugin(simulation::SimulationPlugin)
         .add_plugin(ui::button::ButtonPlugin)
+        .add_plugin(actions::ActionsPlugin)
         .add_plugin(controls::ControlsPlugin)
         .add_plugin(camera::ZoomPlugin)
         .add_plugin(ui::UiPlugin)
         .add_plugin(level::LevelPlugin)
         .add_plugin(fuel::FuelPlugin)
+        .add_plugin(gravity::GravityPlugin)
+        .add_plugin(fluid::FluidPlugin)
         .add_plugin(hover::HoverPlugin)
+        .add_plugin(jump::JumpPlugin)
         .add_plugin(fuel_ball::FuelBallPlugin)
+        .add_plugin(fuel_budget::FuelBudgetPlugin)
         .add_plugin(player::PlayerPlugin)
         .add_plugin(reset::ResetPlugin)
+        .add_plugin(respawn::RespawnPlugin)
         .add_plugin(load_level::LoadLevelPlugin)
         .add_plugin(next_level::NextLevelPlugin)
         .add_plugin(r#continue::ContinuePlugin)
+        .add_plugin(retry::RetryPlugin)
+        .add_plugin(pause_menu::PauseMenuPlugin)
         .add_plugin(pause::PausePlugin)
         .add_plugin(main_menu::MainMenuPlugin)
         .add_plugin(level_editor::LevelEditorPlugin)
         .add_plugin(level_order::LevelOrderPlugin)
+        .add_plugin(trigger::TriggerPlugin)
+        .add_plugin(profile::ProfilePlugin)
+        .add_plugin(settings::SettingsPlugin)
+        .add_plugin(timer::TimerPlugin)
+        .add_plugin(ghost::GhostPlugin)
+        .add_plugin(crumble::CrumblePlugin)
+        .add_plugin(replay::ReplayPlugin)
+        .add_plugin(stats::StatsPlugin)
         .add_startup_system(setup)
-        .add_system(collision::handle_player_collisions.in_set(OnUpdate(GameState::Playing)));
+        .add_event::<collision::LevelCompleteEvent>()
+        .add_system(
+            collision::handle_player_collisions
+                .in_set(simulation::SimulationSet::Logic)
+                .run_if(in_state(GameState::Playing))
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
 
         if !cfg!(target_family = "wasm") {
             app.add_plugin(AtmospherePlugin);
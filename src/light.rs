@@ -0,0 +1,180 @@
+use bevy::{ecs::system::EntityCommands, prelude::*};
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub const BULB_RADIUS: f32 = 0.1;
+
+fn default_color() -> Color {
+    Color::WHITE
+}
+
+fn default_range() -> f32 {
+    20.0
+}
+
+fn default_radius() -> f32 {
+    0.1
+}
+
+fn default_shadows_enabled() -> bool {
+    true
+}
+
+fn default_outer_angle() -> f32 {
+    std::f32::consts::FRAC_PI_4
+}
+
+/// Parameters shared by every kind of light.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct LightSettings {
+    pub intensity: f32,
+    #[serde(default = "default_color")]
+    pub color: Color,
+    #[serde(default = "default_range")]
+    pub range: f32,
+    /// The radius of the light's source, which softens its shadows.
+    #[serde(default = "default_radius")]
+    pub radius: f32,
+    #[serde(default = "default_shadows_enabled")]
+    pub shadows_enabled: bool,
+}
+
+impl Default for LightSettings {
+    fn default() -> Self {
+        Self {
+            intensity: 800.0,
+            color: default_color(),
+            range: default_range(),
+            radius: default_radius(),
+            shadows_enabled: default_shadows_enabled(),
+        }
+    }
+}
+
+/// The shape of a spot light's cone. Angles are in radians.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Spot {
+    pub direction: Vec3,
+    #[serde(default)]
+    pub inner_angle: f32,
+    #[serde(default = "default_outer_angle")]
+    pub outer_angle: f32,
+}
+
+impl Default for Spot {
+    fn default() -> Self {
+        Self {
+            direction: -Vec3::Y,
+            inner_angle: 0.0,
+            outer_angle: default_outer_angle(),
+        }
+    }
+}
+
+impl Spot {
+    pub fn transform(&self) -> Transform {
+        let direction = self.direction.try_normalize().unwrap_or(-Vec3::Y);
+        let up = if direction.cross(Vec3::Y).length_squared() < f32::EPSILON {
+            Vec3::Z
+        } else {
+            Vec3::Y
+        };
+
+        Transform::IDENTITY.looking_to(direction, up)
+    }
+}
+
+/**
+Whether a light casts shadows according to the level.
+
+The light's own `shadows_enabled` is only set when the player's shadow setting is on too.
+*/
+#[derive(Component)]
+pub struct CastsShadows(pub bool);
+
+pub fn bulb_material(settings: &LightSettings) -> StandardMaterial {
+    let [red, green, blue, _] = settings.color.as_linear_rgba_f32();
+
+    StandardMaterial {
+        base_color: settings.color,
+        emissive: Color::rgba_linear(100.0 * red, 100.0 * green, 100.0 * blue, 0.0),
+        ..default()
+    }
+}
+
+pub fn point_light(settings: &LightSettings) -> PointLight {
+    PointLight {
+        intensity: settings.intensity,
+        color: settings.color,
+        range: settings.range,
+        radius: settings.radius,
+        shadows_enabled: settings.shadows_enabled,
+        ..default()
+    }
+}
+
+pub fn spot_light(settings: &LightSettings, spot: &Spot) -> SpotLight {
+    SpotLight {
+        intensity: settings.intensity,
+        color: settings.color,
+        range: settings.range,
+        radius: settings.radius,
+        shadows_enabled: settings.shadows_enabled,
+        inner_angle: spot.inner_angle,
+        outer_angle: spot.outer_angle,
+        ..default()
+    }
+}
+
+/**
+Spawn a light as a glowing bulb, with the light source as its child.
+
+The bulb has a sensor collider so that it can be selected in the level editor.
+*/
+pub fn spawn<'w, 's, 'a>(
+    commands: &'a mut Commands<'w, 's>,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    position: Vec3,
+    settings: &LightSettings,
+    spot: Option<&Spot>,
+) -> EntityCommands<'w, 's, 'a> {
+    let mut entity_commands = commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: BULB_RADIUS,
+                sectors: 20,
+                stacks: 20,
+            })),
+            material: materials.add(bulb_material(settings)),
+            transform: Transform::from_translation(position),
+            ..default()
+        },
+        Collider::ball(BULB_RADIUS),
+        Sensor,
+    ));
+
+    entity_commands.with_children(|parent| match spot {
+        None => {
+            parent.spawn((
+                PointLightBundle {
+                    point_light: point_light(settings),
+                    ..default()
+                },
+                CastsShadows(settings.shadows_enabled),
+            ));
+        }
+        Some(spot) => {
+            parent.spawn((
+                SpotLightBundle {
+                    spot_light: spot_light(settings, spot),
+                    transform: spot.transform(),
+                    ..default()
+                },
+                CastsShadows(settings.shadows_enabled),
+            ));
+        }
+    });
+
+    entity_commands
+}
@@ -1,11 +1,12 @@
 use bevy::prelude::*;
 
 use crate::{
-    fuel::FuelChanged,
-    level::Level,
+    fuel::{Fuel, FuelChanged},
+    level::{Level, LevelItem},
     level_editor,
     pause::PauseEvent,
     player,
+    settings::Settings,
     ui::{self, UI},
     GameState,
 };
@@ -98,12 +99,14 @@ fn hotreload(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn setup_current_level(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     state: Res<State<GameState>>,
+    settings: Res<Settings>,
     mut ui: ResMut<UI>,
     mut fuel_changed: EventWriter<FuelChanged>,
     mut current_level: ResMut<CurrentLevel>,
@@ -121,7 +124,7 @@ fn setup_current_level(
             DirectionalLightBundle {
                 directional_light: DirectionalLight {
                     illuminance: 10000.0,
-                    shadows_enabled: true,
+                    shadows_enabled: settings.shadows,
                     ..default()
                 },
                 transform: Transform::from_rotation(Quat::from_rotation_x(
@@ -132,11 +135,16 @@ fn setup_current_level(
             InCurrentLevel::NoLocation,
         ));
 
+        // Fuel balls are always spawned in the level editor so that they can still be edited.
+        let fuel_balls =
+            current_level.level.fuel_budget.fuel_balls || state.0 != GameState::Playing;
+
         current_level
             .level
             .structure
             .iter()
             .enumerate()
+            .filter(|(_, item)| fuel_balls || !matches!(item, LevelItem::FuelBall { .. }))
             .for_each(|(index, item)| {
                 item.spawn(&mut commands, &mut meshes, &mut materials)
                     .insert(InCurrentLevel::LevelItem(index));
@@ -149,7 +157,12 @@ fn setup_current_level(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    Transform::from_translation(current_level.level.player_start),
+                    current_level.level.player_start_transform(),
+                    current_level.level.player_start_velocity(),
+                    Fuel::new(
+                        current_level.level.fuel_budget.starting_fuel,
+                        current_level.level.fuel_budget.model,
+                    ),
                     Some(&mut fuel_changed),
                 )
                 .insert(InCurrentLevel::NoLocation);
@@ -171,7 +184,7 @@ fn setup_current_level(
                     &mut commands,
                     &mut meshes,
                     &mut materials,
-                    current_level.level.player_start,
+                    current_level.level.player_start_transform(),
                 );
             }
         }
@@ -1,9 +1,34 @@
 use bevy::prelude::*;
 
-use hover_pill::GamePlugin;
+use hover_pill::{
+    fuel::{FuelModel, FuelModelOverride},
+    replay::ReplayPath,
+    GamePlugin,
+};
 
 fn main() {
     let mut app = App::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--replay" {
+            match args.next() {
+                Some(path) => {
+                    app.insert_resource(ReplayPath(path.into()));
+                }
+                None => eprintln!("--replay needs the path of a replay file"),
+            }
+        } else if arg == "--fuel-model" {
+            match args.next().map(|name| FuelModel::from_name(&name)) {
+                Some(Ok(model)) => {
+                    app.insert_resource(FuelModelOverride(Some(model)));
+                }
+                Some(Err(error)) => eprintln!("unknown fuel model: {}", error),
+                None => eprintln!("--fuel-model needs the name of a fuel model"),
+            }
+        }
+    }
+
     app.add_plugin(GamePlugin);
     app.run();
 }
@@ -1,7 +1,13 @@
 use bevy::prelude::*;
 
 use crate::{
-    level_editor, load_level, ui,
+    level_editor,
+    level_order::CurrentLevelOrder,
+    load_level::{CurrentLevel, InCurrentLevel, LoadEvent},
+    pause::PauseEvent,
+    profile::Profile,
+    settings::Settings,
+    ui,
     ui::{button, UI},
     GameState,
 };
@@ -66,7 +72,7 @@ pub fn create(asset_server: &AssetServer, commands: &mut Commands) -> Entity {
                     ));
                 })
                 .insert(button::OnClick {
-                    callback: play_callback,
+                    callback: level_select_callback,
                 });
 
             parent
@@ -84,56 +90,145 @@ pub fn create(asset_server: &AssetServer, commands: &mut Commands) -> Entity {
                         TextStyle {
                             color: Color::BLACK,
                             font_size: 30.0,
-                            ..style
+                            ..style.clone()
                         },
                     ));
                 })
                 .insert(button::OnClick {
                     callback: level_editor_callback,
                 });
+
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        margin,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Stats",
+                        TextStyle {
+                            color: Color::BLACK,
+                            font_size: 30.0,
+                            ..style.clone()
+                        },
+                    ));
+                })
+                .insert(button::OnClick {
+                    callback: stats_callback,
+                });
+
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        margin,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Settings",
+                        TextStyle {
+                            color: Color::BLACK,
+                            font_size: 30.0,
+                            ..style
+                        },
+                    ));
+                })
+                .insert(button::OnClick {
+                    callback: settings_callback,
+                });
         })
         .id()
 }
 
 pub enum MainMenuEvent {
-    Play,
+    /// Show the level select screen.
+    LevelSelect,
+    Play {
+        path: String,
+    },
     LevelEditor,
+    Stats,
+    Settings,
+    /// Return to the main menu from one of its screens.
+    Back,
+}
+
+/// Leave the level that's being played and go back to the main menu.
+pub struct QuitToMenuEvent {
+    /// Go straight to the level select screen.
+    pub level_select: bool,
 }
 
-fn play_callback(commands: &mut Commands) {
-    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Play));
+fn level_select_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::LevelSelect));
 }
 
 fn level_editor_callback(commands: &mut Commands) {
     commands.add(|world: &mut World| world.send_event(MainMenuEvent::LevelEditor));
 }
 
+fn stats_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Stats));
+}
+
+fn settings_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Settings));
+}
+
+fn back_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Back));
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_events(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut input_events: EventReader<MainMenuEvent>,
     mut ui: ResMut<UI>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut load_event: EventWriter<load_level::LoadEvent>,
+    mut load_event: EventWriter<LoadEvent>,
     mut start_editor_event: EventWriter<level_editor::StartEvent>,
+    profile: Res<Profile>,
+    settings: Res<Settings>,
+    current_level_order: Option<Res<CurrentLevelOrder>>,
 ) {
     if let Some(event) = input_events.iter().last() {
         match event {
-            MainMenuEvent::Play => {
+            MainMenuEvent::LevelSelect => {
                 trace!("play clicked");
 
+                match current_level_order {
+                    Some(current_level_order) => ui::set(&mut commands, &mut ui, |commands| {
+                        ui::level_select::create(
+                            &asset_server,
+                            commands,
+                            &current_level_order.level_order,
+                            &profile.records,
+                        )
+                    }),
+                    None => warn!("no level order to select a level from"),
+                }
+            }
+            MainMenuEvent::Play { path } => {
+                trace!("level {:?} selected", path);
+
                 ui::clear(&mut commands, &mut ui);
                 ui::camera_off(&mut commands, &mut ui);
 
                 ui::set(&mut commands, &mut ui, |commands| {
-                    ui::fuel_bar::create(commands, &asset_server)
+                    ui::hud::create(commands, &asset_server)
                 });
 
                 next_state.set(GameState::Playing);
 
-                load_event.send(load_level::LoadEvent {
-                    path: "levels/tutorial_1.level.json".into(),
-                });
+                load_event.send(LoadEvent { path: path.clone() });
             }
             MainMenuEvent::LevelEditor => {
                 trace!("level editor clicked");
@@ -145,7 +240,62 @@ fn handle_events(
                     path: "levels/tutorial_1.level.json".into(),
                 })
             }
+            MainMenuEvent::Stats => {
+                trace!("stats clicked");
+
+                ui::set(&mut commands, &mut ui, |commands| {
+                    ui::stats::create(&asset_server, commands, &profile.stats)
+                });
+            }
+            MainMenuEvent::Settings => {
+                trace!("settings clicked");
+
+                ui::set(&mut commands, &mut ui, |commands| {
+                    ui::settings::create(&asset_server, commands, &settings, back_callback)
+                });
+            }
+            MainMenuEvent::Back => {
+                trace!("back clicked");
+
+                ui::set(&mut commands, &mut ui, |commands| {
+                    create(&asset_server, commands)
+                });
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_quit_to_menu(
+    mut commands: Commands,
+    mut quit_events: EventReader<QuitToMenuEvent>,
+    mut ui: ResMut<UI>,
+    overlay: Res<ui::overlay::Overlay>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut main_menu_event: EventWriter<MainMenuEvent>,
+    in_current_level_query: Query<Entity, With<InCurrentLevel>>,
+) {
+    if let Some(QuitToMenuEvent { level_select }) = quit_events.iter().last() {
+        trace!("quit to menu");
+
+        for entity in &in_current_level_query {
+            commands.entity(entity).despawn_recursive();
         }
+        commands.remove_resource::<CurrentLevel>();
+
+        /* The controls are left disabled by any overlay that paused them. */
+        pause_event.send(PauseEvent::Unpause);
+        next_state.set(GameState::MainMenu);
+
+        ui::overlay::remove(&mut commands, &mut ui, &overlay);
+        ui::camera_on(&mut commands, &mut ui);
+
+        main_menu_event.send(if *level_select {
+            MainMenuEvent::LevelSelect
+        } else {
+            MainMenuEvent::Back
+        });
     }
 }
 
@@ -153,6 +303,9 @@ pub struct MainMenuPlugin;
 
 impl Plugin for MainMenuPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<MainMenuEvent>().add_system(handle_events);
+        app.add_event::<MainMenuEvent>()
+            .add_event::<QuitToMenuEvent>()
+            .add_system(handle_events)
+            .add_system(handle_quit_to_menu.before(handle_events));
     }
 }
@@ -1,17 +1,110 @@
 use bevy::prelude::*;
 
 use crate::{
-    level_order::CurrentLevelOrder,
+    level_order::{
+        Chapter, CurrentLevelOrder, LevelOrder, LevelOrders, SelectLevelOrderEvent, Unlock,
+    },
     load_level::{CurrentLevel, LoadEvent},
+    main_menu::QuitToMenuEvent,
     pause::PauseEvent,
+    profile::Profile,
+    timer::format_time,
     ui::{self, UI},
 };
 
+/// The campaign after the current one, if there is one.
+fn next_campaign<'a>(
+    current_level_order: &CurrentLevelOrder,
+    level_orders: &'a LevelOrders,
+) -> Option<&'a (Handle<LevelOrder>, LevelOrder)> {
+    let mut campaigns = level_orders.campaigns.iter();
+
+    campaigns
+        .find(|(handle, _)| handle == &current_level_order.handle)
+        .and_then(|_| campaigns.next())
+}
+
+fn display_campaign_complete(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    ui: &mut UI,
+    current_level_order: &CurrentLevelOrder,
+    level_orders: &LevelOrders,
+    profile: &Profile,
+) {
+    let level_order = &current_level_order.level_order;
+    let records: Vec<_> = level_order
+        .levels()
+        .filter_map(|level| profile.records.level(&level.path))
+        .collect();
+
+    let total_time: f32 = records.iter().filter_map(|record| record.best_time).sum();
+    let stars: u32 = records
+        .iter()
+        .filter_map(|record| record.best_stars)
+        .map(u32::from)
+        .sum();
+
+    let lines = [
+        format!("total best time {}", format_time(total_time)),
+        format!("stars earned {}", stars),
+    ];
+
+    ui::overlay::campaign_complete::display(
+        asset_server,
+        commands,
+        ui,
+        &format!("{} complete!", level_order.name),
+        &lines,
+        next_campaign(current_level_order, level_orders).is_some(),
+    );
+}
+
+/// What the player has to do in `previous` to unlock the chapter with the requirement `unlock`.
+fn unlock_requirement(unlock: Unlock, previous: &Chapter) -> Option<String> {
+    match unlock {
+        Unlock::Always => None,
+        Unlock::CompletePrevious { count } => Some(format!(
+            "complete {} levels of {} to unlock it",
+            count, previous.name
+        )),
+        Unlock::CompleteAllPrevious => Some(format!(
+            "complete every level of {} to unlock it",
+            previous.name
+        )),
+    }
+}
+
+/// Show that the player has reached the end of a chapter, and that the next one is still locked.
+fn display_chapter_complete(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    ui: &mut UI,
+    chapter: &Chapter,
+    next_chapter: &Chapter,
+) {
+    let mut lines = vec![format!("{} is locked", next_chapter.name)];
+    lines.extend(unlock_requirement(next_chapter.unlock, chapter));
+
+    ui::overlay::campaign_complete::display(
+        asset_server,
+        commands,
+        ui,
+        &format!("{} complete!", chapter.name),
+        &lines,
+        false,
+    );
+}
+
+#[allow(clippy::too_many_arguments)]
 fn handle_next_level(
     mut commands: Commands,
+    asset_server: Res<AssetServer>,
     mut input_events: EventReader<ui::overlay::level_complete::NextLevelEvent>,
     current_level_order: Res<CurrentLevelOrder>,
+    level_orders: Res<LevelOrders>,
     current_level: Res<CurrentLevel>,
+    profile: Res<Profile>,
     mut ui: ResMut<UI>,
     overlay: Res<ui::overlay::Overlay>,
     mut pause_event: EventWriter<PauseEvent>,
@@ -22,17 +115,117 @@ fn handle_next_level(
     if let Some(NextLevelEvent) = input_events.iter().last() {
         trace!("next level");
 
-        if let Some(next_level) = current_level_order
-            .level_order
-            .next_level(&current_level.path)
-        {
+        ui::overlay::remove(&mut commands, &mut ui, &overlay);
+
+        let level_order = &current_level_order.level_order;
+        let is_complete = |level_path: &str| profile.records.is_complete(level_path);
+
+        match level_order.next_level(&current_level.path) {
+            Some(next_level) if level_order.is_level_unlocked(next_level, is_complete) => {
+                pause_event.send(PauseEvent::Unpause);
+
+                load_event.send(LoadEvent {
+                    path: String::from(next_level),
+                })
+            }
+            /*
+            The next level is the first of a chapter the player hasn't unlocked yet, so they're
+            sent back to the level select rather than into it.
+            */
+            Some(next_level) => {
+                debug!("next level {:?} is locked", next_level);
+
+                let chapter = level_order
+                    .chapter_index(&current_level.path)
+                    .map(|index| &level_order.chapters[index]);
+                let next_chapter = level_order
+                    .chapter_index(next_level)
+                    .map(|index| &level_order.chapters[index]);
+
+                if let (Some(chapter), Some(next_chapter)) = (chapter, next_chapter) {
+                    display_chapter_complete(
+                        &asset_server,
+                        &mut commands,
+                        &mut ui,
+                        chapter,
+                        next_chapter,
+                    );
+                }
+            }
+            None => {
+                debug!(
+                    "campaign {:?} complete",
+                    current_level_order.level_order.name
+                );
+
+                display_campaign_complete(
+                    &asset_server,
+                    &mut commands,
+                    &mut ui,
+                    &current_level_order,
+                    &level_orders,
+                    &profile,
+                );
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_campaign_complete(
+    mut commands: Commands,
+    mut input_events: EventReader<ui::overlay::campaign_complete::CampaignCompleteEvent>,
+    current_level_order: Res<CurrentLevelOrder>,
+    level_orders: Res<LevelOrders>,
+    mut ui: ResMut<UI>,
+    overlay: Res<ui::overlay::Overlay>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut load_event: EventWriter<LoadEvent>,
+    mut select_level_order_event: EventWriter<SelectLevelOrderEvent>,
+    mut quit_to_menu_event: EventWriter<QuitToMenuEvent>,
+) {
+    use ui::overlay::campaign_complete::CampaignCompleteEvent;
+
+    match input_events.iter().last() {
+        Some(CampaignCompleteEvent::MainMenu) => {
+            trace!("campaign complete, main menu");
+
+            quit_to_menu_event.send(QuitToMenuEvent {
+                level_select: false,
+            });
+        }
+        Some(CampaignCompleteEvent::LevelSelect) => {
+            trace!("campaign complete, level select");
+
+            quit_to_menu_event.send(QuitToMenuEvent { level_select: true });
+        }
+        Some(CampaignCompleteEvent::NextCampaign) => {
+            trace!("campaign complete, next campaign");
+
+            let (handle, level_order) = match next_campaign(&current_level_order, &level_orders) {
+                Some(campaign) => campaign,
+                None => return,
+            };
+
+            let first_level = match level_order.levels().next() {
+                Some(level) => level,
+                None => {
+                    warn!("campaign {:?} has no levels", level_order.name);
+                    return;
+                }
+            };
+
             ui::overlay::remove(&mut commands, &mut ui, &overlay);
             pause_event.send(PauseEvent::Unpause);
 
+            select_level_order_event.send(SelectLevelOrderEvent {
+                handle: handle.clone(),
+            });
             load_event.send(LoadEvent {
-                path: String::from(next_level),
-            })
+                path: first_level.path.clone(),
+            });
         }
+        None => {}
     }
 }
 
@@ -40,6 +233,15 @@ pub struct NextLevelPlugin;
 
 impl Plugin for NextLevelPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(handle_next_level.run_if(resource_exists::<CurrentLevel>()));
+        app.add_system(
+            handle_next_level
+                .run_if(resource_exists::<CurrentLevel>())
+                .run_if(resource_exists::<LevelOrders>()),
+        )
+        .add_system(
+            handle_campaign_complete
+                .run_if(resource_exists::<CurrentLevelOrder>())
+                .run_if(resource_exists::<LevelOrders>()),
+        );
     }
 }
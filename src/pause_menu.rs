@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::InputAction,
+    load_level::CurrentLevel,
+    main_menu::QuitToMenuEvent,
+    pause::PauseEvent,
+    reset::{ResetCause, ResetEvent},
+    settings::Settings,
+    simulation::SimulationPaused,
+    ui::{self, UI},
+    GameState,
+};
+
+#[derive(Resource, Default)]
+struct PauseMenu {
+    open: bool,
+}
+
+/*
+Other overlays pause the controls too, and they have their own way out, so the pause menu isn't
+opened over them.
+*/
+#[allow(clippy::too_many_arguments)]
+fn toggle_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    actions: Res<Input<InputAction>>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut ui: ResMut<UI>,
+    overlay: Res<ui::overlay::Overlay>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut pause_menu_event: EventWriter<ui::overlay::pause_menu::PauseMenuEvent>,
+) {
+    use ui::overlay::pause_menu::PauseMenuEvent;
+
+    if !actions.just_pressed(InputAction::Pause) {
+        return;
+    }
+
+    if pause_menu.open {
+        pause_menu_event.send(PauseMenuEvent::Resume);
+    } else if !overlay.is_displayed() {
+        trace!("pause menu opened");
+
+        pause_menu.open = true;
+        commands.insert_resource(SimulationPaused);
+        pause_event.send(PauseEvent::Pause);
+
+        ui::overlay::pause_menu::display(&asset_server, &mut commands, &mut ui);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    mut input_events: EventReader<ui::overlay::pause_menu::PauseMenuEvent>,
+    mut pause_menu: ResMut<PauseMenu>,
+    mut ui: ResMut<UI>,
+    overlay: Res<ui::overlay::Overlay>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut reset_event: EventWriter<ResetEvent>,
+    mut quit_to_menu_event: EventWriter<QuitToMenuEvent>,
+) {
+    use ui::overlay::pause_menu::PauseMenuEvent;
+
+    let event = match input_events.iter().last() {
+        Some(event) if pause_menu.open => event,
+        _ => return,
+    };
+
+    /* Every option except the settings leaves the pause menu. */
+    if !matches!(
+        event,
+        PauseMenuEvent::Settings | PauseMenuEvent::CloseSettings
+    ) {
+        pause_menu.open = false;
+        commands.remove_resource::<SimulationPaused>();
+    }
+
+    match event {
+        PauseMenuEvent::Resume | PauseMenuEvent::Restart => {
+            trace!("pause menu closed");
+
+            ui::overlay::remove(&mut commands, &mut ui, &overlay);
+            pause_event.send(PauseEvent::Unpause);
+
+            if let PauseMenuEvent::Restart = event {
+                reset_event.send(ResetEvent {
+                    cause: ResetCause::Manual,
+                });
+            }
+        }
+        PauseMenuEvent::Settings => {
+            ui::overlay::remove(&mut commands, &mut ui, &overlay);
+            ui::overlay::pause_menu::display_settings(
+                &asset_server,
+                &mut commands,
+                &mut ui,
+                &settings,
+            );
+        }
+        PauseMenuEvent::CloseSettings => {
+            ui::overlay::remove(&mut commands, &mut ui, &overlay);
+            ui::overlay::pause_menu::display(&asset_server, &mut commands, &mut ui);
+        }
+        PauseMenuEvent::LevelSelect => {
+            quit_to_menu_event.send(QuitToMenuEvent { level_select: true });
+        }
+        PauseMenuEvent::QuitToMenu => {
+            quit_to_menu_event.send(QuitToMenuEvent {
+                level_select: false,
+            });
+        }
+    }
+}
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PauseMenu>().add_systems(
+            (
+                toggle_pause_menu,
+                handle_pause_menu.after(toggle_pause_menu),
+            )
+                .distributive_run_if(resource_exists::<CurrentLevel>())
+                .in_set(OnUpdate(GameState::Playing)),
+        );
+    }
+}
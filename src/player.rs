@@ -1,12 +1,15 @@
-use bevy::{ecs::system::EntityCommands, input::mouse::MouseMotion, prelude::*};
+use bevy::{ecs::system::EntityCommands, prelude::*};
 use bevy_rapier3d::prelude::*;
 
 use crate::{
     camera::CameraBundle,
     controls::{Controlled, Forward, Speed},
+    fluid::Submerged,
     fuel::{Fuel, FuelChanged},
+    gravity::LocalGravity,
     hover::Hovering,
-    jump::JumpImpulse,
+    jump::{Grounded, JumpImpulse, Jumping},
+    simulation::{SimulationSet, STEP_SECONDS},
 };
 
 pub const CAPSULE_RADIUS: f32 = 0.5;
@@ -21,16 +24,14 @@ pub fn spawn_player<'w, 's, 'a>(
     meshes: &mut Assets<Mesh>,
     materials: &mut Assets<StandardMaterial>,
     transform: Transform,
+    velocity: Vec3,
+    fuel: Fuel,
     fuel_changed: Option<&mut EventWriter<FuelChanged>>,
 ) -> EntityCommands<'w, 's, 'a> {
     let initial_jump_impulse = 5. * Vec3::Y;
 
-    let fuel = Fuel { value: 1.0 };
-
     if let Some(fuel_changed) = fuel_changed {
-        fuel_changed.send(FuelChanged {
-            new_value: fuel.value,
-        });
+        fuel_changed.send(fuel.changed());
     }
 
     let mut entity_commands = commands.spawn((
@@ -52,14 +53,24 @@ pub fn spawn_player<'w, 's, 'a>(
         .insert(ActiveEvents::COLLISION_EVENTS)
         .insert(RigidBody::Dynamic)
         .insert(ColliderMassProperties::Density(1.0))
+        .insert(Velocity::linear(velocity))
         .insert(ExternalForce::default())
         .insert(ExternalImpulse::default())
+        .insert(GravityScale(0.0))
+        .insert(LocalGravity::default())
+        .insert(Damping::default())
+        .insert(Submerged::default())
         .insert(JumpImpulse {
             value: initial_jump_impulse,
         })
+        .insert(Grounded::default())
+        .insert(Jumping::default())
         .insert(LockedAxes::ROTATION_LOCKED)
-        .insert(Forward { value: Vec3::Z })
-        .insert(Speed { value: 3.5 })
+        .insert(Forward {
+            value: transform.rotation * Vec3::Z,
+        })
+        .insert(Ccd::enabled())
+        .insert(Speed::default())
         .insert(Controlled::default())
         .insert(fuel)
         .insert(Hovering { value: false })
@@ -70,57 +81,83 @@ pub fn spawn_player<'w, 's, 'a>(
     entity_commands
 }
 
+/*
+Movement changes the velocity rather than the transform, so that rapier can stop the player at
+walls instead of the player being pushed into them. Only the velocity along the ground is
+controlled, which leaves falling, hovering and jumping to gravity and forces.
+*/
+#[allow(clippy::type_complexity)]
 fn move_controlled(
-    time: Res<Time>,
-    mut controlled_query: Query<(&Controlled, &Speed, &Forward, &mut Transform)>,
+    mut controlled_query: Query<(
+        &Controlled,
+        &Speed,
+        &Forward,
+        &Transform,
+        &mut Velocity,
+        Option<&Submerged>,
+    )>,
 ) {
-    let delta_seconds = time.delta_seconds();
-
-    for (controlled, speed, forward, mut transform) in controlled_query.iter_mut() {
-        let mut movement = Vec3::ZERO;
+    for (controlled, speed, forward, transform, mut velocity, submerged) in
+        controlled_query.iter_mut()
+    {
+        let up = transform.up();
+        let right = forward.value.cross(up).normalize();
+        let mut direction = Vec3::ZERO;
 
         if controlled.forward {
-            movement += delta_seconds * speed.value * forward.value;
+            direction += forward.value;
         }
 
         if controlled.backward {
-            movement += delta_seconds * speed.value * -forward.value;
+            direction -= forward.value;
         }
 
-        let right = forward.value.cross(Vec3::Y).normalize();
-
         if controlled.left {
-            movement += delta_seconds * speed.value * -right;
+            direction -= right;
         }
 
         if controlled.right {
-            movement += delta_seconds * speed.value * right;
+            direction += right;
         }
 
-        transform.translation += movement;
+        let vertical = velocity.linvel.dot(up) * up;
+        let horizontal = velocity.linvel - vertical;
+
+        let max_speed = speed.max * submerged.map_or(1.0, Submerged::speed_multiplier);
+        let (target, rate) = match direction.try_normalize() {
+            Some(direction) => (max_speed * direction, speed.acceleration),
+            None => (Vec3::ZERO, speed.deceleration),
+        };
+
+        velocity.linvel = vertical + move_towards(horizontal, target, rate * STEP_SECONDS);
+    }
+}
+
+fn move_towards(current: Vec3, target: Vec3, max_delta: f32) -> Vec3 {
+    let delta = target - current;
+
+    if delta.length() <= max_delta {
+        target
+    } else {
+        current + max_delta * delta.normalize()
     }
 }
 
 fn rotate_controlled(
-    mut mouse_motion_events: EventReader<MouseMotion>,
-    mut query: Query<(&Controlled, &mut Forward, &mut Transform, &Children)>,
+    mut query: Query<(&mut Controlled, &mut Forward, &mut Transform, &Children)>,
     mut camera_query: Query<&mut Transform, (With<Camera>, Without<Controlled>)>,
 ) {
-    let delta: Vec2 = mouse_motion_events
-        .iter()
-        .fold(Vec2::ZERO, |delta, mouse_motion_event| {
-            delta + mouse_motion_event.delta
-        });
+    for (mut controlled, mut forward, mut transform, children) in query.iter_mut() {
+        let delta = std::mem::take(&mut controlled.look);
 
-    for (controlled, mut forward, mut transform, children) in query.iter_mut() {
-        if controlled.rotating {
-            let rotation = Quat::from_rotation_y(0.005 * -delta.x);
+        if delta != Vec2::ZERO {
+            let rotation = Quat::from_axis_angle(transform.up(), -delta.x);
             forward.value = rotation * forward.value;
             transform.rotate(rotation);
 
             for child in children.iter() {
                 if let Ok(mut camera_transform) = camera_query.get_mut(*child) {
-                    let camera_rotation = Quat::from_axis_angle(Vec3::X, 0.005 * delta.y);
+                    let camera_rotation = Quat::from_axis_angle(Vec3::X, delta.y);
 
                     camera_transform.rotate_around(Vec3::ZERO, camera_rotation);
                 }
@@ -133,7 +170,10 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(move_controlled)
-            .add_system(rotate_controlled);
+        app.add_systems(
+            (move_controlled, rotate_controlled.before(move_controlled))
+                .in_set(SimulationSet::Logic)
+                .in_schedule(CoreSchedule::FixedUpdate),
+        );
     }
 }
@@ -0,0 +1,103 @@
+use bevy::{app::AppExit, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    records::Records, replay::not_watching_replay, reset::ResetEvent, save,
+    simulation::SimulationSet, stats::Stats, GameState,
+};
+
+const PROFILE_FILE: &str = "profile.json";
+
+/// The version of the profile format written by this version of the game.
+const PROFILE_VERSION: u32 = 1;
+
+/// Everything about the player that's kept between sessions.
+#[derive(Resource, Serialize, Deserialize)]
+pub struct Profile {
+    pub version: u32,
+    #[serde(default)]
+    pub records: Records,
+    #[serde(default)]
+    pub stats: Stats,
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self {
+            version: PROFILE_VERSION,
+            records: Records::default(),
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl Profile {
+    pub fn load() -> Self {
+        let mut profile: Profile = save::read(PROFILE_FILE);
+
+        if profile.version > PROFILE_VERSION {
+            warn!(
+                "profile version {} is newer than this game's {}, so some of it may be lost",
+                profile.version, PROFILE_VERSION
+            );
+        }
+
+        profile.version = PROFILE_VERSION;
+        profile
+    }
+
+    pub fn save(&self) {
+        save::write(PROFILE_FILE, self);
+    }
+}
+
+fn save_profile(profile: Res<Profile>) {
+    profile.save();
+}
+
+/// Whether the profile has changed during a simulation step and hasn't been saved since.
+#[derive(Resource, Default)]
+struct Unsaved(bool);
+
+fn mark_unsaved_on_reset(mut unsaved: ResMut<Unsaved>, mut reset_events: EventReader<ResetEvent>) {
+    if reset_events.iter().last().is_some() {
+        unsaved.0 = true;
+    }
+}
+
+/* Saving blocks on the disk, so it's done once the frame's steps have run rather than in one. */
+fn save_unsaved(profile: Res<Profile>, mut unsaved: ResMut<Unsaved>) {
+    if unsaved.0 {
+        unsaved.0 = false;
+        profile.save();
+    }
+}
+
+fn save_on_exit(profile: Res<Profile>, mut exit_events: EventReader<AppExit>) {
+    if exit_events.iter().last().is_some() {
+        profile.save();
+    }
+}
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        /*
+        Closing a browser tab doesn't send `AppExit`, so the profile is also saved whenever a level
+        is reset or left, which limits what's lost on the web to the current attempt.
+        */
+        app.insert_resource(Profile::load())
+            .init_resource::<Unsaved>()
+            .add_system(save_profile.in_schedule(OnEnter(GameState::MainMenu)))
+            .add_system(save_profile.in_schedule(OnExit(GameState::Playing)))
+            .add_system(
+                mark_unsaved_on_reset
+                    .in_set(SimulationSet::Record)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(not_watching_replay)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_systems((save_unsaved, save_on_exit).in_base_set(CoreSet::Last));
+    }
+}
@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+/**
+Limits on a run. A run meets the thresholds if it's within every limit that's set, so thresholds
+with no limits are always met.
+*/
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct StarThresholds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_seconds: Option<f32>,
+    /// The fuel that must be left at the end of the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_fuel: Option<f32>,
+    /// How many times the level can be reset before the run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_resets: Option<u32>,
+}
+
+impl StarThresholds {
+    fn met_by(&self, run: &Run) -> bool {
+        !matches!(self.max_seconds, Some(max) if run.seconds > max)
+            && !matches!(self.min_fuel, Some(min) if run.fuel < min)
+            && !matches!(self.max_resets, Some(max) if run.resets > max)
+    }
+}
+
+/// How a level's runs are rated. Completing the level earns one star.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct StarRating {
+    pub two_stars: StarThresholds,
+    /// Three stars also need the thresholds for two stars to be met.
+    pub three_stars: StarThresholds,
+}
+
+/// A completed run of a level.
+pub struct Run {
+    pub seconds: f32,
+    pub fuel: f32,
+    pub resets: u32,
+}
+
+impl StarRating {
+    pub fn rate(&self, run: &Run) -> u8 {
+        if !self.two_stars.met_by(run) {
+            1
+        } else if !self.three_stars.met_by(run) {
+            2
+        } else {
+            3
+        }
+    }
+}
+
+/// Stars out of three, such as `★★☆`.
+pub fn format_stars(stars: u8) -> String {
+    (1..=3)
+        .map(|star| if star <= stars { '★' } else { '☆' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rating() -> StarRating {
+        StarRating {
+            two_stars: StarThresholds {
+                max_seconds: Some(20.0),
+                ..Default::default()
+            },
+            three_stars: StarThresholds {
+                max_seconds: Some(10.0),
+                min_fuel: Some(0.5),
+                max_resets: Some(0),
+            },
+        }
+    }
+
+    fn run(seconds: f32, fuel: f32, resets: u32) -> Run {
+        Run {
+            seconds,
+            fuel,
+            resets,
+        }
+    }
+
+    #[test]
+    fn limits_are_inclusive() {
+        assert_eq!(rating().rate(&run(20.0, 0.0, 5)), 2);
+        assert_eq!(rating().rate(&run(10.0, 0.5, 0)), 3);
+    }
+
+    #[test]
+    fn missing_any_limit_loses_the_star() {
+        assert_eq!(rating().rate(&run(20.01, 1.0, 0)), 1);
+        assert_eq!(rating().rate(&run(10.01, 1.0, 0)), 2);
+        assert_eq!(rating().rate(&run(5.0, 0.49, 0)), 2);
+        assert_eq!(rating().rate(&run(5.0, 1.0, 1)), 2);
+    }
+
+    #[test]
+    fn three_stars_need_the_two_star_limits() {
+        let rating = StarRating {
+            two_stars: StarThresholds {
+                max_resets: Some(0),
+                ..Default::default()
+            },
+            three_stars: StarThresholds::default(),
+        };
+
+        assert_eq!(rating.rate(&run(5.0, 1.0, 1)), 1);
+        assert_eq!(rating.rate(&run(5.0, 1.0, 0)), 3);
+    }
+
+    #[test]
+    fn a_rating_without_limits_gives_three_stars() {
+        assert_eq!(StarRating::default().rate(&run(1000.0, 0.0, 100)), 3);
+    }
+
+    #[test]
+    fn format_stars_fills_from_the_left() {
+        assert_eq!(format_stars(1), "★☆☆");
+        assert_eq!(format_stars(3), "★★★");
+    }
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// The player's best run of a level.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LevelRecord {
+    /// In seconds.
+    pub best_time: Option<f32>,
+    /// The split times of the best run, in seconds.
+    #[serde(default)]
+    pub best_splits: Vec<f32>,
+    /// The most stars earned in a run, out of three.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_stars: Option<u8>,
+}
+
+/// Personal bests, keyed by level path.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Records {
+    pub levels: HashMap<String, LevelRecord>,
+}
+
+impl Records {
+    pub fn level(&self, level_path: &str) -> Option<&LevelRecord> {
+        self.levels.get(level_path)
+    }
+
+    /// Whether the player has finished the level at least once.
+    pub fn is_complete(&self, level_path: &str) -> bool {
+        self.level(level_path)
+            .and_then(|record| record.best_time)
+            .is_some()
+    }
+}
@@ -0,0 +1,257 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{app::AppExit, ecs::event::ManualEventReader, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controls::{ActiveSet, Controlled},
+    hover::HoverEvent,
+    load_level::{CurrentLevel, LoadEvent},
+    player::Player,
+    reset::{ResetCause, ResetEvent},
+    save,
+    simulation::SimulationSet,
+    ui::{self, UI},
+    GameState,
+};
+
+/// Version 2 records how far the player looked in radians, rather than in pixels.
+const REPLAY_VERSION: u32 = 2;
+
+/// Where the most recent run of a level is saved.
+const LATEST_REPLAY_FILE: &str = "replays/latest.replay.json";
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+/// The player's input during one simulation step.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ReplayFrame {
+    pub controlled: Controlled,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hover: Vec<HoverEvent>,
+    /// Whether the player asked to reset. Resets caused by the level aren't recorded.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub reset: bool,
+}
+
+/**
+The input of every simulation step of a level, from when the player was spawned.
+
+Playing it back with the same version of the game reproduces the run.
+*/
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Replay {
+    pub version: u32,
+    pub level_path: String,
+    pub frames: Vec<ReplayFrame>,
+}
+
+impl Replay {
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let bytes = std::fs::read(path).map_err(|error| error.to_string())?;
+        let replay: Replay = serde_json::from_slice(&bytes).map_err(|error| error.to_string())?;
+
+        if replay.version != REPLAY_VERSION {
+            return Err(format!("unsupported replay version {}", replay.version));
+        }
+
+        Ok(replay)
+    }
+}
+
+/// The replay file given with `--replay`.
+#[derive(Resource)]
+pub struct ReplayPath(pub PathBuf);
+
+#[derive(Resource, Default)]
+struct Recorder {
+    replay: Option<Replay>,
+    hover_reader: ManualEventReader<HoverEvent>,
+    reset_reader: ManualEventReader<ResetEvent>,
+}
+
+impl Recorder {
+    fn save(&mut self) {
+        if let Some(replay) = self.replay.take() {
+            if !replay.frames.is_empty() {
+                debug!("saving replay of {:?}", replay.level_path);
+                save::write(LATEST_REPLAY_FILE, &replay);
+            }
+        }
+    }
+}
+
+/// Playing back a replay, instead of taking input from the player.
+#[derive(Resource)]
+pub struct Playback {
+    replay: Replay,
+    frame: usize,
+}
+
+/// A run condition for systems that record the player's progress, which a replay mustn't change.
+pub fn not_watching_replay(playback: Option<Res<Playback>>) -> bool {
+    playback.is_none()
+}
+
+/*
+Events that are sent during a step come from the level rather than the player, so they're skipped
+when the step ends, leaving only the events that were sent between steps for the next frame.
+*/
+fn record(
+    mut recorder: ResMut<Recorder>,
+    current_level: Res<CurrentLevel>,
+    hover_events: Res<Events<HoverEvent>>,
+    reset_events: Res<Events<ResetEvent>>,
+    spawned_query: Query<(), Added<Player>>,
+    player_query: Query<&Controlled, With<Player>>,
+) {
+    let recorder = recorder.as_mut();
+
+    if !spawned_query.is_empty() {
+        recorder.save();
+        recorder.replay = Some(Replay {
+            version: REPLAY_VERSION,
+            level_path: current_level.path.clone(),
+            frames: Vec::new(),
+        });
+    }
+
+    let hover = recorder.hover_reader.iter(&hover_events).copied().collect();
+    let reset = recorder.reset_reader.iter(&reset_events).last().is_some();
+
+    if let (Some(replay), Ok(controlled)) = (&mut recorder.replay, player_query.get_single()) {
+        replay.frames.push(ReplayFrame {
+            controlled: *controlled,
+            hover,
+            reset,
+        });
+    }
+}
+
+fn skip_level_events(
+    mut recorder: ResMut<Recorder>,
+    hover_events: Res<Events<HoverEvent>>,
+    reset_events: Res<Events<ResetEvent>>,
+) {
+    let recorder = recorder.as_mut();
+    recorder.hover_reader.clear(&hover_events);
+    recorder.reset_reader.clear(&reset_events);
+}
+
+fn save_on_complete(
+    mut recorder: ResMut<Recorder>,
+    mut level_complete_events: EventReader<crate::collision::LevelCompleteEvent>,
+) {
+    if level_complete_events.iter().last().is_some() {
+        recorder.save();
+    }
+}
+
+fn save_on_exit(mut recorder: ResMut<Recorder>, mut exit_events: EventReader<AppExit>) {
+    if exit_events.iter().last().is_some() {
+        recorder.save();
+    }
+}
+
+fn start_playback(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut ui: ResMut<UI>,
+    replay_path: Res<ReplayPath>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut load_event: EventWriter<LoadEvent>,
+) {
+    let replay = match Replay::read(&replay_path.0) {
+        Ok(replay) => replay,
+        Err(error) => {
+            error!("couldn't read replay {:?}: {}", replay_path.0, error);
+            return;
+        }
+    };
+
+    info!(
+        "playing {} frames of {:?}",
+        replay.frames.len(),
+        replay.level_path
+    );
+
+    ui::camera_off(&mut commands, &mut ui);
+    ui::set(&mut commands, &mut ui, |commands| {
+        ui::hud::create(commands, &asset_server)
+    });
+
+    next_state.set(GameState::Playing);
+    load_event.send(LoadEvent {
+        path: replay.level_path.clone(),
+    });
+
+    commands.insert_resource(Playback { replay, frame: 0 });
+}
+
+fn play(
+    mut playback: ResMut<Playback>,
+    mut hover_event: EventWriter<HoverEvent>,
+    mut reset_event: EventWriter<ResetEvent>,
+    mut player_query: Query<&mut Controlled, With<Player>>,
+) {
+    let mut controlled = match player_query.get_single_mut() {
+        Ok(controlled) => controlled,
+        Err(_) => return,
+    };
+
+    let frame = match playback.replay.frames.get(playback.frame) {
+        Some(frame) => frame,
+        None => {
+            if playback.frame == playback.replay.frames.len() {
+                info!("replay finished");
+                controlled.reset();
+                playback.frame += 1;
+            }
+            return;
+        }
+    };
+
+    *controlled = frame.controlled;
+    hover_event.send_batch(frame.hover.iter().copied());
+    if frame.reset {
+        reset_event.send(ResetEvent {
+            cause: ResetCause::Manual,
+        });
+    }
+
+    playback.frame += 1;
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        if app.world.contains_resource::<ReplayPath>() {
+            app.configure_set(ActiveSet.run_if(not_watching_replay))
+                .add_startup_system(start_playback.in_base_set(StartupSet::PostStartup))
+                .add_system(
+                    play.in_set(SimulationSet::Input)
+                        .run_if(resource_exists::<Playback>())
+                        .in_schedule(CoreSchedule::FixedUpdate),
+                );
+        } else {
+            app.init_resource::<Recorder>()
+                .add_system(
+                    record
+                        .in_set(SimulationSet::Input)
+                        .run_if(in_state(GameState::Playing))
+                        .run_if(resource_exists::<CurrentLevel>())
+                        .in_schedule(CoreSchedule::FixedUpdate),
+                )
+                .add_system(
+                    skip_level_events
+                        .in_set(SimulationSet::Record)
+                        .in_schedule(CoreSchedule::FixedUpdate),
+                )
+                .add_system(save_on_complete)
+                .add_system(save_on_exit.in_base_set(CoreSet::Last));
+        }
+    }
+}
@@ -1,26 +1,49 @@
 use bevy::prelude::*;
+use bevy_rapier3d::prelude::Velocity;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    controls::Controlled,
+    controls::{Controlled, Forward},
     fuel::{add_fuel, Fuel, FuelChanged},
     load_level::CurrentLevel,
+    respawn::respawn,
+    simulation::{SimulationEvents, SimulationSet},
     GameState,
 };
 
-pub struct ResetEvent;
+/// Why the level was reset.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ResetCause {
+    /// The player pressed reset.
+    Manual,
+    /// The player retried after failing the level.
+    Retry,
+    /// The player hit an avoid wall.
+    Avoid,
+    /// The player left the level.
+    OutOfBounds,
+}
+
+pub struct ResetEvent {
+    pub cause: ResetCause,
+}
 
 fn reset_player(
     mut reset_events: EventReader<ResetEvent>,
     current_level: Res<CurrentLevel>,
-    mut query: Query<(&mut Transform, &mut Fuel), With<Controlled>>,
+    mut query: Query<(&mut Transform, &mut Forward, &mut Velocity, &mut Fuel), With<Controlled>>,
     mut fuel_changed_event: EventWriter<FuelChanged>,
 ) {
-    if let Some(ResetEvent) = reset_events.iter().last() {
-        for (mut transform, mut fuel) in &mut query {
-            transform.translation = current_level.level.player_start;
+    if reset_events.iter().last().is_some() {
+        for (mut transform, mut forward, mut velocity, mut fuel) in &mut query {
+            *transform = current_level.level.player_start_transform();
+            forward.value = transform.rotation * Vec3::Z;
+            *velocity = Velocity::linear(current_level.level.player_start_velocity());
 
-            let amount = 1.0 - fuel.value;
+            fuel.unlock();
+            let amount = current_level.level.fuel_budget.starting_fuel - fuel.value;
             add_fuel(&mut fuel, amount, &mut fuel_changed_event);
+            fuel.used = 0.0;
         }
     }
 }
@@ -29,10 +52,17 @@ pub struct ResetPlugin;
 
 impl Plugin for ResetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_event::<ResetEvent>().add_system(
+        app.add_simulation_event::<ResetEvent>().add_system(
             reset_player
-                .in_set(OnUpdate(GameState::Playing))
-                .run_if(resource_exists::<CurrentLevel>()),
+                /*
+                The player respawns by being made dynamic again and reset, which have to happen in
+                the same step, or they'd fall from where they died for a step.
+                */
+                .after(respawn)
+                .in_set(SimulationSet::Logic)
+                .run_if(in_state(GameState::Playing))
+                .run_if(resource_exists::<CurrentLevel>())
+                .in_schedule(CoreSchedule::FixedUpdate),
         );
     }
 }
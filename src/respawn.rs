@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+use crate::{
+    controls::Controlled,
+    hover::{end_hover, HoverEvent, Hovering},
+    load_level::CurrentLevel,
+    player::Player,
+    reset::{ResetCause, ResetEvent},
+    simulation::{SimulationEvents, SimulationSet, STEP_SECONDS},
+    GameState,
+};
+
+/// How long the player flashes after dying.
+pub const FLASH_SECONDS: f32 = 0.3;
+
+/// When the screen starts fading out.
+pub const FADE_OUT_SECONDS: f32 = 0.35;
+
+/// When the player respawns, once the screen has faded out.
+pub const RESPAWN_SECONDS: f32 = 0.7;
+
+/// When the screen has faded back in and the sequence is over.
+pub const FADE_IN_SECONDS: f32 = 1.0;
+
+/// How far the camera shakes when the player dies.
+const SHAKE_DISTANCE: f32 = 0.15;
+
+/// How far the player can go past the furthest item of a level before they're out of bounds.
+const OUT_OF_BOUNDS_DISTANCE: f32 = 50.0;
+
+/// Sent when the player hits something that kills them, or leaves the level.
+pub struct DeathEvent {
+    pub cause: ResetCause,
+}
+
+/**
+The death and respawn sequence, from the moment the player died.
+
+The player is frozen until they respawn, which pressing jump or reset does straight away.
+*/
+#[derive(Component)]
+pub struct Dying {
+    pub cause: ResetCause,
+    pub seconds: f32,
+    pub respawned: bool,
+}
+
+impl Dying {
+    /// How dark the screen is, from `0.0` to `1.0`.
+    pub fn fade(&self) -> f32 {
+        if self.seconds < FADE_OUT_SECONDS {
+            0.0
+        } else if !self.respawned {
+            ((self.seconds - FADE_OUT_SECONDS) / (RESPAWN_SECONDS - FADE_OUT_SECONDS)).min(1.0)
+        } else {
+            1.0 - ((self.seconds - RESPAWN_SECONDS) / (FADE_IN_SECONDS - RESPAWN_SECONDS))
+                .clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// Which attempt at the current level this is, counting from one.
+#[derive(Resource, Default)]
+pub struct Attempts {
+    pub count: u32,
+}
+
+fn count_attempts(
+    current_level: Res<CurrentLevel>,
+    mut reset_events: EventReader<ResetEvent>,
+    mut attempts: ResMut<Attempts>,
+) {
+    if current_level.is_changed() {
+        attempts.count = 1;
+    } else if reset_events.iter().last().is_some() {
+        attempts.count += 1;
+    }
+}
+
+/*
+A player who falls off a level would otherwise fall until they pressed reset, which the stats would
+count as a manual reset. Leaving the level is treated as a death instead, so that it's counted as
+its own cause of failure. The margin is wide so that it never cuts a route on the level short.
+*/
+fn check_bounds(
+    current_level: Res<CurrentLevel>,
+    query: Query<&Transform, (With<Player>, Without<Dying>)>,
+    mut death_event: EventWriter<DeathEvent>,
+) {
+    let (min, max) = current_level.level.bounds();
+    let margin = Vec3::splat(OUT_OF_BOUNDS_DISTANCE);
+
+    for transform in &query {
+        let position = transform.translation;
+
+        if position.cmplt(min - margin).any() || position.cmpgt(max + margin).any() {
+            debug!("player left the level at {}", position);
+            death_event.send(DeathEvent {
+                cause: ResetCause::OutOfBounds,
+            });
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn die(
+    mut commands: Commands,
+    mut death_events: EventReader<DeathEvent>,
+    mut query: Query<
+        (
+            Entity,
+            &mut RigidBody,
+            &mut Velocity,
+            &mut Hovering,
+            &mut ExternalForce,
+            Option<&Dying>,
+        ),
+        With<Player>,
+    >,
+) {
+    if let Some(DeathEvent { cause }) = death_events.iter().last() {
+        for (entity, mut rigid_body, mut velocity, mut hovering, mut external_force, dying) in
+            &mut query
+        {
+            if matches!(
+                dying,
+                Some(Dying {
+                    respawned: false,
+                    ..
+                })
+            ) {
+                continue;
+            }
+
+            debug!("player {:?} died", entity);
+
+            *rigid_body = RigidBody::Fixed;
+            *velocity = Velocity::zero();
+            end_hover(&mut hovering, &mut external_force);
+
+            commands.entity(entity).insert(Dying {
+                cause: *cause,
+                seconds: 0.0,
+                respawned: false,
+            });
+        }
+    }
+}
+
+/*
+Skipping reads the player's jump and reset input, which replays record, so that a replay skips at
+the same step as the run it was recorded from.
+*/
+pub fn respawn(
+    mut commands: Commands,
+    mut hover_events: EventReader<HoverEvent>,
+    mut reset_events: EventReader<ResetEvent>,
+    mut reset_event: EventWriter<ResetEvent>,
+    mut query: Query<(Entity, &mut Dying, &mut RigidBody, &mut Controlled)>,
+) {
+    let skipped = hover_events
+        .iter()
+        .any(|event| matches!(event, HoverEvent::Start));
+    let reset = reset_events.iter().last().is_some();
+
+    for (entity, mut dying, mut rigid_body, mut controlled) in &mut query {
+        dying.seconds += STEP_SECONDS;
+
+        if !dying.respawned && (reset || skipped || dying.seconds >= RESPAWN_SECONDS) {
+            debug!("player {:?} respawned", entity);
+
+            /* A reset from the player respawns them already. */
+            if !reset {
+                reset_event.send(ResetEvent { cause: dying.cause });
+            }
+
+            *rigid_body = RigidBody::Dynamic;
+            controlled.reset();
+
+            dying.respawned = true;
+            dying.seconds = dying.seconds.max(RESPAWN_SECONDS);
+        }
+
+        if dying.seconds >= FADE_IN_SECONDS {
+            commands.entity(entity).remove::<Dying>();
+        }
+    }
+}
+
+/// Flash the player's material as they die.
+fn flash(
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    query: Query<(&Handle<StandardMaterial>, Option<&Dying>), With<Player>>,
+    mut flashing: Local<bool>,
+) {
+    for (material, dying) in &query {
+        let intensity = match dying {
+            Some(dying) if !dying.respawned => (1.0 - dying.seconds / FLASH_SECONDS).max(0.0),
+            _ => 0.0,
+        };
+
+        if intensity > 0.0 || *flashing {
+            if let Some(material) = materials.get_mut(material) {
+                material.emissive = Color::rgb(4.0 * intensity, 0.6 * intensity, 0.4 * intensity);
+            }
+        }
+
+        *flashing = intensity > 0.0;
+    }
+}
+
+/*
+The camera is moved by an offset that's removed again on the next frame, so that it doesn't
+interfere with zooming, which also moves the camera.
+*/
+fn shake_camera(
+    time: Res<Time>,
+    player_query: Query<(&Dying, &Children), With<Player>>,
+    mut camera_query: Query<&mut Transform, With<Camera>>,
+    mut applied: Local<Option<(Entity, Vec3)>>,
+) {
+    if let Some((camera, offset)) = applied.take() {
+        if let Ok(mut transform) = camera_query.get_mut(camera) {
+            transform.translation -= offset;
+        }
+    }
+
+    for (dying, children) in &player_query {
+        if dying.respawned {
+            continue;
+        }
+
+        let strength = SHAKE_DISTANCE * (1.0 - dying.seconds / RESPAWN_SECONDS).max(0.0);
+        let t = time.elapsed_seconds();
+        let offset = strength * Vec3::new((53.0 * t).sin(), (67.0 * t).cos(), 0.0);
+
+        for child in children.iter() {
+            if let Ok(mut transform) = camera_query.get_mut(*child) {
+                transform.translation += offset;
+                *applied = Some((*child, offset));
+            }
+        }
+    }
+}
+
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_simulation_event::<DeathEvent>()
+            .init_resource::<Attempts>()
+            .add_systems(
+                (
+                    respawn,
+                    check_bounds,
+                    die.after(respawn).after(check_bounds),
+                    count_attempts,
+                )
+                    .in_set(SimulationSet::Logic)
+                    .distributive_run_if(in_state(GameState::Playing))
+                    .distributive_run_if(resource_exists::<CurrentLevel>())
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_systems((flash, shake_camera).in_set(OnUpdate(GameState::Playing)));
+    }
+}
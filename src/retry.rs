@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use crate::{
+    pause::PauseEvent,
+    reset::{ResetCause, ResetEvent},
+    ui::{self, UI},
+};
+
+fn handle_retry(
+    mut input_events: EventReader<ui::overlay::level_failed::RetryEvent>,
+    mut commands: Commands,
+    mut ui: ResMut<UI>,
+    overlay: Res<ui::overlay::Overlay>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut reset_event: EventWriter<ResetEvent>,
+) {
+    use ui::overlay::level_failed::RetryEvent;
+
+    if let Some(RetryEvent) = input_events.iter().last() {
+        trace!("retry");
+
+        ui::overlay::remove(&mut commands, &mut ui, &overlay);
+        pause_event.send(PauseEvent::Unpause);
+        reset_event.send(ResetEvent {
+            cause: ResetCause::Retry,
+        });
+    }
+}
+
+pub struct RetryPlugin;
+
+impl Plugin for RetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(handle_retry);
+    }
+}
@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/**
+Where save files are kept. On native platforms they're files in the platform's per-user
+configuration directory, and on the web they're entries in `localStorage`.
+*/
+#[cfg(not(target_family = "wasm"))]
+mod storage {
+    use std::{io::Write, path::PathBuf};
+
+    /// The directory that the game's save files are kept in, following each platform's convention.
+    fn save_dir() -> Option<PathBuf> {
+        let env_dir = |name: &str| std::env::var_os(name).map(PathBuf::from);
+
+        let config_dir = if cfg!(target_os = "windows") {
+            env_dir("APPDATA")
+        } else if cfg!(target_os = "macos") {
+            env_dir("HOME").map(|home| home.join("Library").join("Application Support"))
+        } else {
+            env_dir("XDG_CONFIG_HOME").or_else(|| env_dir("HOME").map(|home| home.join(".config")))
+        };
+
+        config_dir.map(|config_dir| config_dir.join("hover-pill"))
+    }
+
+    fn path(name: &str) -> Result<PathBuf, String> {
+        save_dir()
+            .map(|save_dir| save_dir.join(name))
+            .ok_or_else(|| "no directory to save in".to_string())
+    }
+
+    pub fn load(name: &str) -> Result<Option<Vec<u8>>, String> {
+        match std::fs::read(path(name)?) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
+    /*
+    The new contents are written to a temporary file, which then replaces the old file in one
+    step, so a crash part way through a write leaves the old file intact.
+    */
+    pub fn store(name: &str, bytes: &[u8]) -> Result<(), String> {
+        let path = path(name)?;
+        let temporary_path = path.with_extension("tmp");
+
+        let result = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                let mut file = std::fs::File::create(&temporary_path)?;
+                file.write_all(bytes)?;
+                file.sync_all()
+            })
+            .and_then(|_| std::fs::rename(&temporary_path, &path));
+
+        result.map_err(|error| error.to_string())
+    }
+
+    pub fn rename(from: &str, to: &str) -> Result<(), String> {
+        std::fs::rename(path(from)?, path(to)?).map_err(|error| error.to_string())
+    }
+}
+
+#[cfg(target_family = "wasm")]
+mod storage {
+    fn local_storage() -> Result<web_sys::Storage, String> {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .ok_or_else(|| "localStorage isn't available".to_string())
+    }
+
+    fn key(name: &str) -> String {
+        format!("hover-pill/{}", name)
+    }
+
+    pub fn load(name: &str) -> Result<Option<Vec<u8>>, String> {
+        local_storage()?
+            .get_item(&key(name))
+            .map(|value| value.map(String::into_bytes))
+            .map_err(|error| format!("{:?}", error))
+    }
+
+    /// Setting an item in `localStorage` either replaces it completely or fails, so it's atomic.
+    pub fn store(name: &str, bytes: &[u8]) -> Result<(), String> {
+        let value = std::str::from_utf8(bytes).map_err(|error| error.to_string())?;
+
+        local_storage()?
+            .set_item(&key(name), value)
+            .map_err(|error| format!("{:?}", error))
+    }
+
+    pub fn rename(from: &str, to: &str) -> Result<(), String> {
+        let storage = local_storage()?;
+        let value = storage
+            .get_item(&key(from))
+            .map_err(|error| format!("{:?}", error))?
+            .ok_or_else(|| format!("{:?} doesn't exist", from))?;
+
+        storage
+            .set_item(&key(to), &value)
+            .and_then(|_| storage.remove_item(&key(from)))
+            .map_err(|error| format!("{:?}", error))
+    }
+}
+
+fn backup_name(name: &str) -> String {
+    format!("{}.bak", name)
+}
+
+fn parse<T: DeserializeOwned>(name: &str) -> Result<Option<T>, String> {
+    match storage::load(name)? {
+        Some(bytes) => serde_json::from_slice(&bytes)
+            .map(Some)
+            .map_err(|error| error.to_string()),
+        None => Ok(None),
+    }
+}
+
+/**
+Read the save file called `name`, or `None` if it doesn't exist.
+
+If the file can't be read, it's set aside as `<name>.corrupt` so that it isn't overwritten, and
+the backup made by the last successful [`write`] is used instead.
+
+`name` is relative to the save directory and may include subdirectories.
+*/
+pub fn try_read<T: DeserializeOwned>(name: &str) -> Option<T> {
+    match parse(name) {
+        Ok(value) => value,
+        Err(error) => {
+            warn!("couldn't read {:?}: {}", name, error);
+
+            if let Err(error) = storage::rename(name, &format!("{}.corrupt", name)) {
+                warn!("couldn't set aside {:?}: {}", name, error);
+            }
+
+            match parse(&backup_name(name)) {
+                Ok(Some(value)) => {
+                    info!("recovered {:?} from its backup", name);
+                    Some(value)
+                }
+                Ok(None) => None,
+                Err(error) => {
+                    warn!("couldn't read the backup of {:?}: {}", name, error);
+                    None
+                }
+            }
+        }
+    }
+}
+
+/// Read the save file called `name`, or the default value if it doesn't exist or can't be read.
+pub fn read<T: DeserializeOwned + Default>(name: &str) -> T {
+    try_read(name).unwrap_or_default()
+}
+
+/// Replace the save file called `name`, keeping a backup of the previous version.
+pub fn write<T: Serialize>(name: &str, value: &T) {
+    let result = serde_json::to_vec_pretty(value)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| {
+            /* Only a file that can be read is worth keeping as a backup. */
+            if let Ok(Some(previous)) = storage::load(name) {
+                if serde_json::from_slice::<serde::de::IgnoredAny>(&previous).is_ok() {
+                    storage::store(&backup_name(name), &previous)?;
+                }
+            }
+
+            storage::store(name, &bytes)
+        });
+
+    if let Err(error) = result {
+        warn!("couldn't save {:?}: {}", name, error);
+    }
+}
+
+/**
+Replace the save file called `name` without keeping a backup, and without any whitespace.
+
+This is for large files that are cheap to lose, such as ghosts, which would otherwise soon fill the
+few megabytes of `localStorage` that the web allows and stop the profile from being saved.
+*/
+pub fn write_compact<T: Serialize>(name: &str, value: &T) {
+    let result = serde_json::to_vec(value)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| storage::store(name, &bytes));
+
+    if let Err(error) = result {
+        warn!("couldn't save {:?}: {}", name, error);
+    }
+}
+
+#[cfg(all(test, not(target_family = "wasm")))]
+mod tests {
+    use std::{path::PathBuf, sync::Once};
+
+    use super::*;
+
+    /// Point every platform's save directory at a temporary directory, once for all the tests.
+    fn save_dir() -> PathBuf {
+        static INIT: Once = Once::new();
+
+        let config_dir =
+            std::env::temp_dir().join(format!("hover-pill-test-{}", std::process::id()));
+
+        INIT.call_once(|| {
+            std::env::set_var("XDG_CONFIG_HOME", &config_dir);
+            std::env::set_var("HOME", &config_dir);
+            std::env::set_var("APPDATA", &config_dir);
+        });
+
+        let save_dir = if cfg!(target_os = "macos") {
+            config_dir.join("Library").join("Application Support")
+        } else {
+            config_dir
+        };
+
+        save_dir.join("hover-pill")
+    }
+
+    #[test]
+    fn written_values_are_read_back() {
+        save_dir();
+
+        write("round_trip.json", &vec![1, 2, 3]);
+
+        assert_eq!(try_read::<Vec<u32>>("round_trip.json"), Some(vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn missing_files_are_none() {
+        save_dir();
+
+        assert_eq!(try_read::<u32>("missing.json"), None);
+        assert_eq!(read::<u32>("missing.json"), 0);
+    }
+
+    #[test]
+    fn corrupt_files_are_set_aside_and_recovered_from_the_backup() {
+        let save_dir = save_dir();
+
+        write("recover.json", &1);
+        write("recover.json", &2);
+        storage::store("recover.json", b"{ not json").unwrap();
+
+        assert_eq!(try_read::<u32>("recover.json"), Some(1));
+        assert!(!save_dir.join("recover.json").exists());
+        assert_eq!(
+            std::fs::read(save_dir.join("recover.json.corrupt")).unwrap(),
+            b"{ not json"
+        );
+    }
+
+    #[test]
+    fn corrupt_files_without_a_backup_are_none() {
+        let save_dir = save_dir();
+
+        storage::store("no_backup.json", b"{ not json").unwrap();
+
+        assert_eq!(try_read::<u32>("no_backup.json"), None);
+        assert!(save_dir.join("no_backup.json.corrupt").exists());
+    }
+
+    #[test]
+    fn unreadable_files_arent_kept_as_backups() {
+        save_dir();
+
+        write("bad_backup.json", &1);
+        write("bad_backup.json", &2);
+        storage::store("bad_backup.json", b"{ not json").unwrap();
+        write("bad_backup.json", &3);
+
+        assert_eq!(try_read::<u32>("bad_backup.json"), Some(3));
+        assert_eq!(try_read::<u32>("bad_backup.json.bak"), Some(1));
+    }
+}
@@ -0,0 +1,220 @@
+use bevy::{
+    prelude::*,
+    window::{PrimaryWindow, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{actions::Bindings, light::CastsShadows, save};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// How the game's window is shown.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DisplayMode {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl DisplayMode {
+    const ALL: [DisplayMode; 3] = [
+        DisplayMode::Windowed,
+        DisplayMode::Borderless,
+        DisplayMode::Fullscreen,
+    ];
+
+    pub fn window_mode(self) -> WindowMode {
+        match self {
+            DisplayMode::Windowed => WindowMode::Windowed,
+            DisplayMode::Borderless => WindowMode::BorderlessFullscreen,
+            DisplayMode::Fullscreen => WindowMode::Fullscreen,
+        }
+    }
+}
+
+/// The player's preferences, which are kept between sessions.
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// How far the view turns for each pixel the mouse moves, in radians.
+    pub mouse_sensitivity: f32,
+    /// The fraction of the camera's distance that one step of the scroll wheel zooms by.
+    pub zoom_step: f32,
+    /// The camera's vertical field of view, in degrees.
+    pub field_of_view: f32,
+    /// Whether the sun and the level's lights cast shadows.
+    pub shadows: bool,
+    pub display_mode: DisplayMode,
+    /// From `0.0` to `1.0`.
+    pub volume: f32,
+    pub bindings: Bindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            mouse_sensitivity: 0.005,
+            zoom_step: 0.08,
+            field_of_view: 60.0,
+            shadows: true,
+            display_mode: DisplayMode::default(),
+            volume: 1.0,
+            bindings: Bindings::default(),
+        }
+    }
+}
+
+/// A setting that can be changed from the settings menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Setting {
+    MouseSensitivity,
+    ZoomStep,
+    FieldOfView,
+    Shadows,
+    DisplayMode,
+    Volume,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 6] = [
+        Setting::MouseSensitivity,
+        Setting::ZoomStep,
+        Setting::FieldOfView,
+        Setting::Shadows,
+        Setting::DisplayMode,
+        Setting::Volume,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Setting::MouseSensitivity => "mouse sensitivity",
+            Setting::ZoomStep => "zoom step",
+            Setting::FieldOfView => "field of view",
+            Setting::Shadows => "shadows",
+            Setting::DisplayMode => "window",
+            Setting::Volume => "volume",
+        }
+    }
+}
+
+fn step(value: f32, step: f32, steps: i32, min: f32, max: f32) -> f32 {
+    /* Rounding to the step stops repeated steps from accumulating floating-point error. */
+    ((value / step).round() + steps as f32).clamp(min / step, max / step) * step
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        save::read(SETTINGS_FILE)
+    }
+
+    pub fn save(&self) {
+        save::write(SETTINGS_FILE, self);
+    }
+
+    /// Move a setting `steps` steps up or down, wrapping around for settings that are a choice.
+    pub fn adjust(&mut self, setting: Setting, steps: i32) {
+        match setting {
+            Setting::MouseSensitivity => {
+                self.mouse_sensitivity = step(self.mouse_sensitivity, 0.0005, steps, 0.0005, 0.02)
+            }
+            Setting::ZoomStep => self.zoom_step = step(self.zoom_step, 0.01, steps, 0.01, 0.3),
+            Setting::FieldOfView => {
+                self.field_of_view = step(self.field_of_view, 5.0, steps, 40.0, 110.0)
+            }
+            Setting::Shadows => self.shadows = !self.shadows,
+            Setting::DisplayMode => {
+                let modes = DisplayMode::ALL.len() as i32;
+                let index = DisplayMode::ALL
+                    .iter()
+                    .position(|mode| *mode == self.display_mode)
+                    .unwrap_or(0) as i32;
+
+                self.display_mode = DisplayMode::ALL[(index + steps).rem_euclid(modes) as usize];
+            }
+            Setting::Volume => self.volume = step(self.volume, 0.1, steps, 0.0, 1.0),
+        }
+    }
+
+    /// The value of a setting, for display.
+    pub fn describe(&self, setting: Setting) -> String {
+        match setting {
+            Setting::MouseSensitivity => format!("{:.4}", self.mouse_sensitivity),
+            Setting::ZoomStep => format!("{:.2}", self.zoom_step),
+            Setting::FieldOfView => format!("{:.0}°", self.field_of_view),
+            Setting::Shadows => String::from(if self.shadows { "on" } else { "off" }),
+            Setting::DisplayMode => format!("{:?}", self.display_mode).to_lowercase(),
+            Setting::Volume => format!("{:.0}%", 100.0 * self.volume),
+        }
+    }
+}
+
+fn apply_camera_settings(
+    settings: Res<Settings>,
+    added_query: Query<(), Added<Camera3d>>,
+    mut query: Query<&mut Projection, With<Camera3d>>,
+) {
+    if settings.is_changed() || !added_query.is_empty() {
+        for mut projection in &mut query {
+            if let Projection::Perspective(perspective) = projection.as_mut() {
+                perspective.fov = settings.field_of_view.to_radians();
+            }
+        }
+    }
+}
+
+/* Level lights only cast shadows if the level asks for them as well. */
+fn apply_shadow_settings(
+    settings: Res<Settings>,
+    mut directional_query: Query<&mut DirectionalLight>,
+    mut point_query: Query<(&mut PointLight, Ref<CastsShadows>)>,
+    mut spot_query: Query<(&mut SpotLight, Ref<CastsShadows>)>,
+) {
+    if settings.is_changed() {
+        for mut directional_light in &mut directional_query {
+            directional_light.shadows_enabled = settings.shadows;
+        }
+    }
+
+    for (mut point_light, casts_shadows) in &mut point_query {
+        if settings.is_changed() || casts_shadows.is_changed() {
+            point_light.shadows_enabled = settings.shadows && casts_shadows.0;
+        }
+    }
+
+    for (mut spot_light, casts_shadows) in &mut spot_query {
+        if settings.is_changed() || casts_shadows.is_changed() {
+            spot_light.shadows_enabled = settings.shadows && casts_shadows.0;
+        }
+    }
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if settings.is_changed() {
+        for mut window in &mut query {
+            window.mode = settings.display_mode.window_mode();
+        }
+    }
+}
+
+fn save_settings(settings: Res<Settings>) {
+    if settings.is_changed() && !settings.is_added() {
+        settings.save();
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Settings::load()).add_systems((
+            apply_camera_settings,
+            apply_shadow_settings,
+            apply_window_settings,
+            save_settings,
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::{
+    ecs::{event::Event, schedule::ExecutorKind},
+    prelude::*,
+};
+use bevy_rapier3d::prelude::*;
+
+/// The length of a simulation step, in seconds.
+pub const STEP_SECONDS: f32 = 1.0 / 60.0;
+
+/**
+The phases of a simulation step, which run in [`CoreSchedule::FixedUpdate`].
+
+Everything that affects the outcome of a run belongs in a step, so that a run can be reproduced
+from its input regardless of the frame rate.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Hash, SystemSet)]
+pub enum SimulationSet {
+    /// Systems that feed input into the step.
+    Input,
+    /// Gameplay systems that act on the input and on the previous step's collisions.
+    Logic,
+    Physics(PhysicsSet),
+    /// Systems that observe the result of the step.
+    Record,
+}
+
+/// While this resource exists, simulation steps are skipped, freezing physics and gameplay.
+#[derive(Resource)]
+pub struct SimulationPaused;
+
+pub trait SimulationEvents {
+    /**
+    Add an event that's read by simulation systems.
+
+    Ordinary events are cleared every frame, so a step could miss events that are sent on frames
+    where no step runs. These events are cleared every step instead.
+    */
+    fn add_simulation_event<T: Event>(&mut self) -> &mut Self;
+}
+
+impl SimulationEvents for App {
+    fn add_simulation_event<T: Event>(&mut self) -> &mut Self {
+        if !self.world.contains_resource::<Events<T>>() {
+            self.init_resource::<Events<T>>().add_system(
+                Events::<T>::update_system
+                    .in_set(SimulationSet::Record)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            );
+        }
+
+        self
+    }
+}
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FixedTime::new_from_secs(STEP_SECONDS))
+            .insert_resource(RapierConfiguration {
+                timestep_mode: TimestepMode::Fixed {
+                    dt: STEP_SECONDS,
+                    substeps: 1,
+                },
+                ..default()
+            })
+            .add_plugin(
+                RapierPhysicsPlugin::<NoUserData>::default().with_default_system_setup(false),
+            )
+            .edit_schedule(CoreSchedule::FixedUpdate, |schedule| {
+                /*
+                The multi-threaded executor can run ambiguously ordered systems in a different
+                order each step, which is enough to change the result of floating-point
+                arithmetic. The single-threaded executor always uses the same order.
+                */
+                schedule.set_executor_kind(ExecutorKind::SingleThreaded);
+
+                schedule.configure_sets(
+                    (
+                        SimulationSet::Input,
+                        SimulationSet::Logic,
+                        SimulationSet::Physics(PhysicsSet::SyncBackend),
+                        SimulationSet::Physics(PhysicsSet::SyncBackendFlush),
+                        SimulationSet::Physics(PhysicsSet::StepSimulation),
+                        SimulationSet::Physics(PhysicsSet::Writeback),
+                        SimulationSet::Record,
+                    )
+                        .chain(),
+                );
+
+                /* Every phase of a step is skipped while the simulation is paused. */
+                for simulation_set in [
+                    SimulationSet::Input,
+                    SimulationSet::Logic,
+                    SimulationSet::Physics(PhysicsSet::SyncBackend),
+                    SimulationSet::Physics(PhysicsSet::SyncBackendFlush),
+                    SimulationSet::Physics(PhysicsSet::StepSimulation),
+                    SimulationSet::Physics(PhysicsSet::Writeback),
+                    SimulationSet::Record,
+                ] {
+                    schedule.configure_set(
+                        simulation_set.run_if(not(resource_exists::<SimulationPaused>())),
+                    );
+                }
+
+                for physics_set in [
+                    PhysicsSet::SyncBackend,
+                    PhysicsSet::SyncBackendFlush,
+                    PhysicsSet::StepSimulation,
+                    PhysicsSet::Writeback,
+                ] {
+                    schedule.add_systems(
+                        RapierPhysicsPlugin::<NoUserData>::get_systems(physics_set.clone())
+                            .in_set(SimulationSet::Physics(physics_set)),
+                    );
+                }
+            });
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    collision::LevelCompleteEvent,
+    controls::ControlsConfig,
+    load_level::CurrentLevel,
+    player::Player,
+    profile::Profile,
+    replay::not_watching_replay,
+    reset::{ResetCause, ResetEvent},
+    respawn::{respawn, Dying},
+    simulation::{SimulationEvents, SimulationSet, STEP_SECONDS},
+    GameState,
+};
+
+/// Sent when the player picks up fuel.
+pub struct FuelCollectedEvent {
+    pub amount: f32,
+}
+
+/// How the player has got on with a level, over every time they've played it.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct LevelStats {
+    /// Each time the player started the level, or reset it.
+    pub attempts: u32,
+    #[serde(default)]
+    pub resets: BTreeMap<ResetCause, u32>,
+    pub seconds_played: f32,
+    pub fuel_collected: f32,
+    pub completions: u32,
+}
+
+impl LevelStats {
+    pub fn resets(&self, cause: ResetCause) -> u32 {
+        self.resets.get(&cause).copied().unwrap_or(0)
+    }
+}
+
+/// Statistics for tuning the difficulty of levels, keyed by level path.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Stats {
+    pub levels: HashMap<String, LevelStats>,
+}
+
+impl Stats {
+    fn level_mut(&mut self, level_path: &str) -> &mut LevelStats {
+        self.levels.entry(level_path.to_string()).or_default()
+    }
+}
+
+fn count_starts(
+    current_level: Res<CurrentLevel>,
+    query: Query<(), Added<Player>>,
+    mut profile: ResMut<Profile>,
+) {
+    if !query.is_empty() {
+        profile.stats.level_mut(&current_level.path).attempts += 1;
+    }
+}
+
+/*
+A reset while the player is dying skips to their respawn, so it's counted as a death rather than a
+reset. This has to run before the respawn is handled.
+*/
+fn count_resets(
+    current_level: Res<CurrentLevel>,
+    mut reset_events: EventReader<ResetEvent>,
+    dying_query: Query<&Dying, With<Player>>,
+    mut profile: ResMut<Profile>,
+) {
+    if let Some(ResetEvent { cause }) = reset_events.iter().last() {
+        let cause = match dying_query.get_single() {
+            Ok(dying) if !dying.respawned => dying.cause,
+            _ => *cause,
+        };
+
+        let level_stats = profile.stats.level_mut(&current_level.path);
+        level_stats.attempts += 1;
+        *level_stats.resets.entry(cause).or_default() += 1;
+    }
+}
+
+fn count_time_played(
+    current_level: Res<CurrentLevel>,
+    controls_config: Res<ControlsConfig>,
+    player_query: Query<(), With<Player>>,
+    mut profile: ResMut<Profile>,
+) {
+    if controls_config.enabled && !player_query.is_empty() {
+        profile.stats.level_mut(&current_level.path).seconds_played += STEP_SECONDS;
+    }
+}
+
+fn count_fuel_collected(
+    current_level: Res<CurrentLevel>,
+    mut fuel_collected_events: EventReader<FuelCollectedEvent>,
+    mut profile: ResMut<Profile>,
+) {
+    let amount: f32 = fuel_collected_events.iter().map(|event| event.amount).sum();
+
+    if amount > 0.0 {
+        profile.stats.level_mut(&current_level.path).fuel_collected += amount;
+    }
+}
+
+fn count_completions(
+    current_level: Res<CurrentLevel>,
+    mut level_complete_events: EventReader<LevelCompleteEvent>,
+    mut profile: ResMut<Profile>,
+) {
+    if level_complete_events.iter().last().is_some() {
+        profile.stats.level_mut(&current_level.path).completions += 1;
+        profile.save();
+    }
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        /* Watching a replay isn't playing, so none of it is counted. */
+        app.add_simulation_event::<FuelCollectedEvent>()
+            .add_systems(
+                (
+                    count_starts,
+                    count_resets.before(respawn),
+                    count_time_played,
+                    count_fuel_collected,
+                )
+                    .in_set(SimulationSet::Logic)
+                    .distributive_run_if(in_state(GameState::Playing))
+                    .distributive_run_if(resource_exists::<CurrentLevel>())
+                    .distributive_run_if(not_watching_replay)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                count_completions
+                    .in_set(OnUpdate(GameState::Playing))
+                    .run_if(resource_exists::<CurrentLevel>())
+                    .run_if(not_watching_replay),
+            );
+    }
+}
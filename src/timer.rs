@@ -0,0 +1,196 @@
+use bevy::prelude::*;
+
+use crate::{
+    collision::LevelCompleteEvent,
+    controls::ControlsConfig,
+    fuel::Fuel,
+    load_level::CurrentLevel,
+    player::Player,
+    profile::Profile,
+    rating::{format_stars, Run},
+    replay::not_watching_replay,
+    reset::ResetEvent,
+    respawn::Attempts,
+    simulation::{SimulationSet, STEP_SECONDS},
+    ui::{self, UI},
+    GameState,
+};
+
+/// The time of the current run of a level.
+#[derive(Resource, Default)]
+pub struct LevelTimer {
+    pub seconds: f32,
+    /// The time of each checkpoint that the player has passed, in order.
+    pub splits: Vec<f32>,
+    pub stopped: bool,
+}
+
+impl LevelTimer {
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    pub fn split(&mut self) {
+        if !self.stopped {
+            self.splits.push(self.seconds);
+        }
+    }
+}
+
+/// Sent when a run of the current level beats the player's best time.
+pub struct NewBestEvent {
+    pub level_path: String,
+    pub seconds: f32,
+}
+
+/// Format a time in seconds as `minutes:seconds.hundredths`.
+pub fn format_time(seconds: f32) -> String {
+    let hundredths = (seconds.max(0.0) * 100.0).round() as u32;
+    format!(
+        "{}:{:02}.{:02}",
+        hundredths / 6000,
+        (hundredths / 100) % 60,
+        hundredths % 100
+    )
+}
+
+/// Format the difference between a time and a personal best, such as `-0.40`.
+pub fn format_delta(seconds: f32, best: f32) -> String {
+    format!("{:+.2}", seconds - best)
+}
+
+fn restart_timer(
+    current_level: Res<CurrentLevel>,
+    mut reset_events: EventReader<ResetEvent>,
+    mut timer: ResMut<LevelTimer>,
+) {
+    if current_level.is_changed() || reset_events.iter().last().is_some() {
+        timer.restart();
+    }
+}
+
+/*
+The timer only runs while the player is in control, so it starts once the level's initial overlay
+is dismissed and doesn't count time spent reading messages.
+*/
+fn tick_timer(
+    controls_config: Res<ControlsConfig>,
+    player_query: Query<(), With<Player>>,
+    mut timer: ResMut<LevelTimer>,
+) {
+    if controls_config.enabled && !timer.stopped && !player_query.is_empty() {
+        timer.seconds += STEP_SECONDS;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_level_complete(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut ui: ResMut<UI>,
+    mut level_complete_events: EventReader<LevelCompleteEvent>,
+    current_level: Res<CurrentLevel>,
+    timer: Res<LevelTimer>,
+    attempts: Res<Attempts>,
+    fuel_query: Query<&Fuel, With<Player>>,
+    mut profile: ResMut<Profile>,
+    mut new_best_event: EventWriter<NewBestEvent>,
+) {
+    if let Some(LevelCompleteEvent) = level_complete_events.iter().last() {
+        let record = profile
+            .records
+            .levels
+            .entry(current_level.path.clone())
+            .or_default();
+        let previous = record.clone();
+        let mut changed = false;
+
+        let mut lines = vec![format!("time {}", format_time(timer.seconds))];
+
+        for (index, split) in timer.splits.iter().enumerate() {
+            let mut line = format!("split {} {}", index + 1, format_time(*split));
+
+            if let Some(best) = previous.best_splits.get(index) {
+                line.push(' ');
+                line.push_str(&format_delta(*split, *best));
+            }
+
+            lines.push(line);
+        }
+
+        match previous.best_time {
+            Some(best_time) if best_time <= timer.seconds => {
+                lines.push(format!("best {}", format_time(best_time)));
+            }
+            _ => {
+                debug!("new best time for {:?}", current_level.path);
+                record.best_time = Some(timer.seconds);
+                record.best_splits = timer.splits.clone();
+                changed = true;
+
+                new_best_event.send(NewBestEvent {
+                    level_path: current_level.path.clone(),
+                    seconds: timer.seconds,
+                });
+
+                lines.push("new best!".to_string());
+            }
+        }
+
+        let stars = current_level.level.stars.map(|rating| {
+            let run = Run {
+                seconds: timer.seconds,
+                fuel: fuel_query.get_single().map_or(0.0, |fuel| fuel.value),
+                resets: attempts.count.saturating_sub(1),
+            };
+
+            rating.rate(&run)
+        });
+
+        if let Some(stars) = stars {
+            match previous.best_stars {
+                Some(best_stars) if best_stars >= stars => {
+                    lines.push(format!("best rating {}", format_stars(best_stars)));
+                }
+                _ => {
+                    debug!("new best rating for {:?}", current_level.path);
+                    record.best_stars = Some(stars);
+                    changed = true;
+                }
+            }
+        }
+
+        if changed {
+            profile.save();
+        }
+
+        ui::overlay::level_complete::display(&asset_server, &mut commands, &mut ui, stars, &lines);
+    }
+}
+
+pub struct TimerPlugin;
+
+impl Plugin for TimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelTimer>()
+            .add_event::<NewBestEvent>()
+            .add_systems(
+                (restart_timer, tick_timer.after(restart_timer))
+                    .in_set(SimulationSet::Logic)
+                    .distributive_run_if(in_state(GameState::Playing))
+                    .distributive_run_if(resource_exists::<CurrentLevel>())
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(
+                handle_level_complete
+                    .in_set(OnUpdate(GameState::Playing))
+                    .run_if(resource_exists::<CurrentLevel>())
+                    /* A replay's time isn't the player's, so it can't set records. */
+                    .run_if(not_watching_replay),
+            );
+    }
+}
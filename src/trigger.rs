@@ -0,0 +1,401 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    collision::{self, LevelCompleteEvent},
+    fuel::{add_fuel, Fuel, FuelChanged},
+    level_editor::LevelEditorState,
+    load_level::InCurrentLevel,
+    pause::PauseEvent,
+    player::Player,
+    reset::ResetEvent,
+    settings::Settings,
+    simulation::{SimulationEvents, SimulationSet, STEP_SECONDS},
+    timer::LevelTimer,
+    ui::{self, UI},
+    GameState,
+};
+
+/// Something a trigger does to the level.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum Action {
+    /// Pause the game and show a message until the player continues.
+    ShowMessage {
+        lines: Vec<String>,
+    },
+    /// Show or hide the level item at index `item`.
+    SetVisible {
+        item: usize,
+        visible: bool,
+    },
+    /// Enable or disable the collider of the level item at index `item`.
+    SetCollider {
+        item: usize,
+        enabled: bool,
+    },
+    AddFuel {
+        amount: f32,
+    },
+    /// Play the sound at `path`, relative to the asset directory.
+    PlaySound {
+        path: String,
+    },
+    CompleteLevel,
+    /// Record a split time for the level timer. Usually used with a `once` trigger as a checkpoint.
+    Split,
+}
+
+impl Action {
+    /// The index of the level item that this action targets.
+    pub fn item(&self) -> Option<usize> {
+        match self {
+            Action::SetVisible { item, .. } => Some(*item),
+            Action::SetCollider { item, .. } => Some(*item),
+            Action::ShowMessage { .. }
+            | Action::AddFuel { .. }
+            | Action::PlaySound { .. }
+            | Action::CompleteLevel
+            | Action::Split => None,
+        }
+    }
+
+    pub fn item_mut(&mut self) -> Option<&mut usize> {
+        match self {
+            Action::SetVisible { item, .. } => Some(item),
+            Action::SetCollider { item, .. } => Some(item),
+            Action::ShowMessage { .. }
+            | Action::AddFuel { .. }
+            | Action::PlaySound { .. }
+            | Action::CompleteLevel
+            | Action::Split => None,
+        }
+    }
+}
+
+/// Actions that run once the player has stayed inside a trigger for `seconds`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Stay {
+    pub seconds: f32,
+    pub actions: Vec<Action>,
+}
+
+#[derive(Component, Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Trigger {
+    /// Ignore the player after their first visit.
+    #[serde(default)]
+    pub once: bool,
+    #[serde(default)]
+    pub on_enter: Vec<Action>,
+    #[serde(default)]
+    pub on_exit: Vec<Action>,
+    #[serde(default)]
+    pub on_stay: Option<Stay>,
+}
+
+impl Trigger {
+    pub fn retain_actions(&mut self, f: impl Fn(&Action) -> bool) {
+        self.on_enter.retain(&f);
+        self.on_exit.retain(&f);
+        if let Some(stay) = &mut self.on_stay {
+            stay.actions.retain(&f);
+        }
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &Action> {
+        self.on_enter
+            .iter()
+            .chain(self.on_exit.iter())
+            .chain(self.on_stay.iter().flat_map(|stay| stay.actions.iter()))
+    }
+
+    pub fn actions_mut(&mut self) -> impl Iterator<Item = &mut Action> {
+        self.on_enter
+            .iter_mut()
+            .chain(self.on_exit.iter_mut())
+            .chain(
+                self.on_stay
+                    .iter_mut()
+                    .flat_map(|stay| stay.actions.iter_mut()),
+            )
+    }
+}
+
+#[derive(Component, Default)]
+struct TriggerState {
+    occupied: bool,
+    seconds_inside: f32,
+    stayed: bool,
+    spent: bool,
+}
+
+#[derive(Bundle)]
+pub struct TriggerBundle {
+    #[bundle]
+    pbr_bundle: PbrBundle,
+    collider: Collider,
+    active_events: ActiveEvents,
+    rigid_body: RigidBody,
+    sensor: Sensor,
+    trigger: Trigger,
+    state: TriggerState,
+}
+
+impl TriggerBundle {
+    pub fn new(
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>,
+        position: Vec3,
+        size: Vec3,
+        trigger: Trigger,
+    ) -> Self {
+        Self {
+            pbr_bundle: PbrBundle {
+                mesh: meshes.add(Mesh::from(shape::Box::new(size.x, size.y, size.z))),
+                material: materials.add(StandardMaterial {
+                    base_color: Color::rgba(1.0, 0.8, 0.2, 0.2),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    ..default()
+                }),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            collider: Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            rigid_body: RigidBody::Fixed,
+            sensor: Sensor,
+            trigger,
+            state: TriggerState::default(),
+        }
+    }
+}
+
+/// Requests that an [`Action`] is performed.
+pub struct ActionEvent {
+    pub action: Action,
+}
+
+fn send_actions(action_event: &mut EventWriter<ActionEvent>, actions: &[Action]) {
+    action_event.send_batch(actions.iter().map(|action| ActionEvent {
+        action: action.clone(),
+    }));
+}
+
+// Trigger volumes are only drawn in the level editor.
+fn hide_triggers(mut query: Query<&mut Visibility, Added<Trigger>>) {
+    for mut visibility in &mut query {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn handle_trigger_collisions(
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<(), With<Player>>,
+    mut trigger_query: Query<(&Trigger, &mut TriggerState)>,
+    mut action_event: EventWriter<ActionEvent>,
+) {
+    for collision_event in collision_events.iter() {
+        let (entity1, entity2, started) = match collision_event {
+            CollisionEvent::Started(entity1, entity2, _) => (entity1, entity2, true),
+            CollisionEvent::Stopped(entity1, entity2, _) => (entity1, entity2, false),
+        };
+
+        let trigger_entity = if player_query.contains(*entity1) {
+            *entity2
+        } else if player_query.contains(*entity2) {
+            *entity1
+        } else {
+            continue;
+        };
+
+        if let Ok((trigger, mut state)) = trigger_query.get_mut(trigger_entity) {
+            if state.spent || state.occupied == started {
+                continue;
+            }
+
+            state.occupied = started;
+
+            if started {
+                debug!("player entered trigger {:?}", trigger_entity);
+                state.seconds_inside = 0.0;
+                state.stayed = false;
+                send_actions(&mut action_event, &trigger.on_enter);
+            } else {
+                debug!("player exited trigger {:?}", trigger_entity);
+                state.spent = trigger.once;
+                send_actions(&mut action_event, &trigger.on_exit);
+            }
+        }
+    }
+}
+
+fn handle_trigger_stay(
+    mut query: Query<(&Trigger, &mut TriggerState)>,
+    mut action_event: EventWriter<ActionEvent>,
+) {
+    for (trigger, mut state) in &mut query {
+        if let (true, false, Some(stay)) = (state.occupied, state.stayed, &trigger.on_stay) {
+            state.seconds_inside += STEP_SECONDS;
+
+            if state.seconds_inside >= stay.seconds {
+                state.stayed = true;
+                send_actions(&mut action_event, &stay.actions);
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_action_events(
+    mut commands: Commands,
+    state: Res<State<GameState>>,
+    asset_server: Res<AssetServer>,
+    audio: Res<Audio>,
+    settings: Res<Settings>,
+    mut ui: ResMut<UI>,
+    mut action_events: EventReader<ActionEvent>,
+    mut pause_event: EventWriter<PauseEvent>,
+    mut fuel_changed: EventWriter<FuelChanged>,
+    mut level_complete_event: EventWriter<LevelCompleteEvent>,
+    mut timer: ResMut<LevelTimer>,
+    mut item_query: Query<(Entity, &InCurrentLevel, &mut Visibility)>,
+    mut fuel_query: Query<&mut Fuel, With<Player>>,
+) {
+    for ActionEvent { action } in action_events.iter() {
+        trace!("action: {:?}", action);
+
+        match action {
+            Action::ShowMessage { lines } => {
+                pause_event.send(PauseEvent::Pause);
+                ui::overlay::level_overview::display(&asset_server, &mut commands, &mut ui, lines);
+            }
+            Action::SetVisible { item, visible } => {
+                for (_, location, mut visibility) in &mut item_query {
+                    if let InCurrentLevel::LevelItem(index) = location {
+                        if index == item {
+                            *visibility = if *visible {
+                                Visibility::Inherited
+                            } else {
+                                Visibility::Hidden
+                            };
+                        }
+                    }
+                }
+            }
+            Action::SetCollider { item, enabled } => {
+                for (entity, location, _) in &item_query {
+                    if let InCurrentLevel::LevelItem(index) = location {
+                        if index == item {
+                            if *enabled {
+                                commands.entity(entity).remove::<ColliderDisabled>();
+                            } else {
+                                commands.entity(entity).insert(ColliderDisabled);
+                            }
+                        }
+                    }
+                }
+            }
+            Action::AddFuel { amount } => {
+                for mut fuel in &mut fuel_query {
+                    add_fuel(&mut fuel, *amount, &mut fuel_changed);
+                }
+            }
+            Action::PlaySound { path } => {
+                audio.play_with_settings(
+                    asset_server.load(path.as_str()),
+                    PlaybackSettings::ONCE.with_volume(settings.volume),
+                );
+            }
+            /* Testing a level in the editor doesn't end it, as there's nothing to move on to. */
+            Action::CompleteLevel if state.0 != GameState::Playing => {
+                debug!("level complete while testing");
+            }
+            Action::CompleteLevel => {
+                collision::complete_level(&mut timer, &mut pause_event, &mut level_complete_event);
+            }
+            Action::Split => {
+                timer.split();
+            }
+        }
+    }
+}
+
+/*
+Triggers start over and the items they showed, hid or made non-solid go back to how the level
+spawned them, which is visible and solid.
+*/
+fn restore_triggers(
+    commands: &mut Commands,
+    trigger_query: &mut Query<(&Trigger, &mut TriggerState)>,
+    item_query: &mut Query<(Entity, &InCurrentLevel, &mut Visibility), Without<Trigger>>,
+) {
+    let mut items = HashSet::new();
+
+    for (trigger, mut state) in trigger_query.iter_mut() {
+        *state = TriggerState::default();
+        items.extend(trigger.actions().filter_map(Action::item));
+    }
+
+    for (entity, location, mut visibility) in item_query.iter_mut() {
+        if let InCurrentLevel::LevelItem(index) = location {
+            if items.contains(index) {
+                *visibility = Visibility::Inherited;
+                commands.entity(entity).remove::<ColliderDisabled>();
+            }
+        }
+    }
+}
+
+fn reset_triggers(
+    mut commands: Commands,
+    mut reset_events: EventReader<ResetEvent>,
+    mut trigger_query: Query<(&Trigger, &mut TriggerState)>,
+    mut item_query: Query<(Entity, &InCurrentLevel, &mut Visibility), Without<Trigger>>,
+) {
+    if reset_events.iter().last().is_some() {
+        restore_triggers(&mut commands, &mut trigger_query, &mut item_query);
+    }
+}
+
+/// The level is edited as it was spawned, rather than as a test left it.
+fn stop_testing_triggers(
+    mut commands: Commands,
+    mut trigger_query: Query<(&Trigger, &mut TriggerState)>,
+    mut item_query: Query<(Entity, &InCurrentLevel, &mut Visibility), Without<Trigger>>,
+) {
+    restore_triggers(&mut commands, &mut trigger_query, &mut item_query);
+}
+
+/// Triggers also run while a level is tested in the editor, so that designers can try them out.
+fn triggers_enabled(
+    state: Res<State<GameState>>,
+    level_editor_state: Res<State<LevelEditorState>>,
+) -> bool {
+    state.0 == GameState::Playing || level_editor_state.0 == LevelEditorState::Testing
+}
+
+pub struct TriggerPlugin;
+
+impl Plugin for TriggerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_simulation_event::<ActionEvent>()
+            .add_system(hide_triggers.in_set(OnUpdate(GameState::Playing)))
+            .add_systems(
+                (
+                    handle_trigger_collisions,
+                    handle_trigger_stay,
+                    handle_action_events
+                        .after(handle_trigger_collisions)
+                        .after(handle_trigger_stay),
+                    reset_triggers.after(handle_action_events),
+                )
+                    .in_set(SimulationSet::Logic)
+                    .distributive_run_if(triggers_enabled)
+                    .in_schedule(CoreSchedule::FixedUpdate),
+            )
+            .add_system(stop_testing_triggers.in_schedule(OnExit(LevelEditorState::Testing)));
+    }
+}
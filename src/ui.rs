@@ -1,6 +1,12 @@
 pub mod button;
 pub mod fuel_bar;
+pub mod hud;
+pub mod level_select;
 pub mod overlay;
+pub mod respawn;
+pub mod settings;
+pub mod stats;
+pub mod timer;
 
 use bevy::prelude::*;
 
@@ -74,6 +80,10 @@ impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UI>()
             .add_plugin(overlay::OverlayPlugin)
-            .add_plugin(fuel_bar::FuelBarPlugin);
+            .add_plugin(fuel_bar::FuelBarPlugin)
+            .add_plugin(level_select::LevelSelectPlugin)
+            .add_plugin(timer::TimerPlugin)
+            .add_plugin(respawn::RespawnPlugin)
+            .add_plugin(settings::SettingsPlugin);
     }
 }
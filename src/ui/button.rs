@@ -1,8 +1,11 @@
 use bevy::prelude::*;
 
+/// What a button does when it's clicked.
+pub type Callback = fn(&mut Commands);
+
 #[derive(Component)]
 pub struct OnClick {
-    pub callback: fn(&mut Commands),
+    pub callback: Callback,
 }
 
 fn handle_on_click(
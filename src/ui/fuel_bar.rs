@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::fuel::FuelChanged;
+use crate::fuel::{FuelChanged, FuelModel};
 
 pub fn create(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
     commands
@@ -45,14 +45,16 @@ pub fn create(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
                         .insert(FuelBar);
                 });
 
-            parent.spawn(TextBundle::from_section(
-                "fuel",
-                TextStyle {
-                    font: asset_server.load("fonts/DejaVuSansMono.ttf"),
-                    font_size: 20.0,
-                    color: Color::WHITE,
-                },
-            ));
+            parent
+                .spawn(TextBundle::from_section(
+                    "fuel",
+                    TextStyle {
+                        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ))
+                .insert(FuelBarLabel);
         })
         .id()
 }
@@ -60,13 +62,49 @@ pub fn create(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
 #[derive(Component)]
 struct FuelBar;
 
+#[derive(Component)]
+struct FuelBarLabel;
+
+/// The bar's fill, colour and label for the given fuel. The overheat model shows heat rather than fuel.
+fn appearance(fuel_changed: &FuelChanged) -> (f32, Color, &'static str) {
+    match (fuel_changed.model, fuel_changed.locked) {
+        (FuelModel::Overheat { .. }, false) => (
+            1.0 - fuel_changed.new_value,
+            Color::rgb(1.0, 0.5, 0.1),
+            "heat",
+        ),
+        (FuelModel::Overheat { .. }, true) => (
+            1.0 - fuel_changed.new_value,
+            Color::rgb(1.0, 0.1, 0.1),
+            "overheated",
+        ),
+        (_, true) => (
+            fuel_changed.new_value,
+            Color::rgb(0.5, 0.5, 0.5),
+            "cooling down",
+        ),
+        (FuelModel::Regenerating { .. }, false) => {
+            (fuel_changed.new_value, Color::rgb(0.3, 0.7, 1.0), "fuel")
+        }
+        _ => (fuel_changed.new_value, Color::rgb(0.4, 0.4, 1.0), "fuel"),
+    }
+}
+
 fn update_fuel_bar(
     mut fuel_changed: EventReader<FuelChanged>,
-    mut query: Query<&mut Style, With<FuelBar>>,
+    mut bar_query: Query<(&mut Style, &mut BackgroundColor), With<FuelBar>>,
+    mut label_query: Query<&mut Text, With<FuelBarLabel>>,
 ) {
-    for fuel_changed in fuel_changed.iter() {
-        for mut style in &mut query {
-            style.size.width = Val::Percent(fuel_changed.new_value * 100.0);
+    if let Some(fuel_changed) = fuel_changed.iter().last() {
+        let (fill, color, label) = appearance(fuel_changed);
+
+        for (mut style, mut background_color) in &mut bar_query {
+            style.size.width = Val::Percent(fill * 100.0);
+            *background_color = color.into();
+        }
+
+        for mut text in &mut label_query {
+            text.sections[0].value = label.to_string();
         }
     }
 }
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use super::{fuel_bar, respawn, timer};
+
+/// The in-game display of the player's fuel, time and attempts.
+pub fn create(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
+    let fuel_bar = fuel_bar::create(commands, asset_server);
+    let timer = timer::create(commands, asset_server);
+    let attempts = respawn::create_attempts(commands, asset_server);
+    let fade = respawn::create_fade(commands);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            ..default()
+        })
+        .push_children(&[fuel_bar, timer, attempts, fade])
+        .id()
+}
@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+
+use crate::{
+    level_order::LevelOrder, main_menu::MainMenuEvent, rating::format_stars, records::Records,
+    timer::format_time, ui::button,
+};
+
+/// A level that can be played from the level select screen.
+#[derive(Component)]
+pub struct LevelButton {
+    pub path: String,
+}
+
+fn back_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Back));
+}
+
+fn row(name: &str, status: &str, best: &str) -> String {
+    format!("{:<20}{:>10}{:>10}", name, status, best)
+}
+
+/// The stars and time of the level's best run, if it's been completed.
+fn level_row(name: &str, level_path: &str, records: &Records) -> String {
+    match records.level(level_path) {
+        Some(record) if record.best_time.is_some() => row(
+            name,
+            &record.best_stars.map(format_stars).unwrap_or_default(),
+            &record.best_time.map(format_time).unwrap_or_default(),
+        ),
+        _ => row(name, "", ""),
+    }
+}
+
+pub fn create(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    level_order: &LevelOrder,
+    records: &Records,
+) -> Entity {
+    let style = TextStyle {
+        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+        font_size: 20.0,
+        color: Color::BLACK,
+    };
+
+    let is_complete = |level_path: &str| records.is_complete(level_path);
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.4, 0.7, 1.0).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                ..TextBundle::from_section(
+                    &level_order.name,
+                    TextStyle {
+                        font_size: 40.0,
+                        color: Color::WHITE,
+                        ..style.clone()
+                    },
+                )
+            });
+
+            for (chapter_index, chapter) in level_order.chapters.iter().enumerate() {
+                let unlocked = level_order.is_chapter_unlocked(chapter_index, is_complete);
+
+                parent.spawn(TextBundle {
+                    style: Style {
+                        margin: UiRect::new(
+                            Val::Px(0.0),
+                            Val::Px(0.0),
+                            Val::Px(10.0),
+                            Val::Px(5.0),
+                        ),
+                        ..default()
+                    },
+                    ..TextBundle::from_section(
+                        if unlocked {
+                            chapter.name.clone()
+                        } else {
+                            format!("{} (locked)", chapter.name)
+                        },
+                        TextStyle {
+                            font_size: 26.0,
+                            color: Color::WHITE,
+                            ..style.clone()
+                        },
+                    )
+                });
+
+                for level in &chapter.levels {
+                    let node_style = Style {
+                        padding: UiRect::all(Val::Px(5.0)),
+                        margin: UiRect::all(Val::Px(2.0)),
+                        ..default()
+                    };
+
+                    if unlocked {
+                        parent
+                            .spawn(ButtonBundle {
+                                style: node_style,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    level_row(&level.name, &level.path, records),
+                                    style.clone(),
+                                ));
+                            })
+                            .insert(LevelButton {
+                                path: level.path.clone(),
+                            });
+                    } else {
+                        parent
+                            .spawn(NodeBundle {
+                                style: node_style,
+                                background_color: Color::rgba(1.0, 1.0, 1.0, 0.4).into(),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    row(&level.name, "locked", ""),
+                                    TextStyle {
+                                        color: Color::DARK_GRAY,
+                                        ..style.clone()
+                                    },
+                                ));
+                            });
+                    }
+                }
+            }
+
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Back",
+                        TextStyle {
+                            font_size: 30.0,
+                            ..style.clone()
+                        },
+                    ));
+                })
+                .insert(button::OnClick {
+                    callback: back_callback,
+                });
+        })
+        .id()
+}
+
+fn handle_level_buttons(
+    query: Query<(&Interaction, &LevelButton), Changed<Interaction>>,
+    mut main_menu_event: EventWriter<MainMenuEvent>,
+) {
+    for (interaction, level_button) in &query {
+        if let Interaction::Clicked = interaction {
+            main_menu_event.send(MainMenuEvent::Play {
+                path: level_button.path.clone(),
+            });
+        }
+    }
+}
+
+pub struct LevelSelectPlugin;
+
+impl Plugin for LevelSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(handle_level_buttons);
+    }
+}
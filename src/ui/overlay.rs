@@ -1,5 +1,8 @@
+pub mod campaign_complete;
 pub mod level_complete;
+pub mod level_failed;
 pub mod level_overview;
+pub mod pause_menu;
 
 use bevy::prelude::*;
 
@@ -10,6 +13,12 @@ pub struct Overlay {
     entity: Option<Entity>,
 }
 
+impl Overlay {
+    pub fn is_displayed(&self) -> bool {
+        self.entity.is_some()
+    }
+}
+
 pub fn display(
     commands: &mut Commands,
     ui: &mut UI,
@@ -58,6 +67,9 @@ impl Plugin for OverlayPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<Overlay>()
             .add_plugin(level_overview::LevelOverviewPlugin)
-            .add_plugin(level_complete::LevelCompletePlugin);
+            .add_plugin(level_complete::LevelCompletePlugin)
+            .add_plugin(campaign_complete::CampaignCompletePlugin)
+            .add_plugin(level_failed::LevelFailedPlugin)
+            .add_plugin(pause_menu::PauseMenuPlugin);
     }
 }
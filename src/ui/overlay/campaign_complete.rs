@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::ui::{button, UI};
+
+pub enum CampaignCompleteEvent {
+    MainMenu,
+    LevelSelect,
+    NextCampaign,
+}
+
+fn main_menu_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(CampaignCompleteEvent::MainMenu))
+}
+
+fn level_select_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(CampaignCompleteEvent::LevelSelect))
+}
+
+fn next_campaign_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(CampaignCompleteEvent::NextCampaign))
+}
+
+/**
+Show that the player has reached the end of a campaign, or of a chapter whose next chapter is still
+locked, offering the next campaign if there is one.
+*/
+pub fn display(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    ui: &mut UI,
+    title: &str,
+    lines: &[String],
+    next_campaign: bool,
+) {
+    super::display(commands, ui, |parent| {
+        let style = TextStyle {
+            font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+            font_size: 40.0,
+            color: Color::WHITE,
+        };
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        color: Color::GOLD,
+                        ..style.clone()
+                    },
+                ));
+
+                for line in lines {
+                    parent.spawn(TextBundle::from_section(
+                        line,
+                        TextStyle {
+                            font_size: 30.0,
+                            ..style.clone()
+                        },
+                    ));
+                }
+
+                let mut buttons: Vec<(&str, button::Callback)> = vec![
+                    ("main menu", main_menu_callback),
+                    ("level select", level_select_callback),
+                ];
+                if next_campaign {
+                    buttons.push(("next campaign", next_campaign_callback));
+                }
+
+                for (label, callback) in buttons {
+                    parent
+                        .spawn(ButtonBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    top: Val::Px(30.0),
+                                    ..Default::default()
+                                },
+                                padding: UiRect::all(Val::Px(10.0)),
+                                ..Default::default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                label,
+                                TextStyle {
+                                    font_size: 30.0,
+                                    color: Color::BLACK,
+                                    ..style.clone()
+                                },
+                            ));
+                        })
+                        .insert(button::OnClick { callback });
+                }
+            });
+    });
+}
+
+pub struct CampaignCompletePlugin;
+
+impl Plugin for CampaignCompletePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<CampaignCompleteEvent>();
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::*;
 
-use crate::ui::{button, UI};
+use crate::{
+    rating::format_stars,
+    ui::{button, UI},
+};
 
 pub struct NextLevelEvent;
 
@@ -8,7 +11,14 @@ fn next_level_callback(commands: &mut Commands) {
     commands.add(|world: &mut World| world.send_event(NextLevelEvent))
 }
 
-pub fn display(asset_server: &AssetServer, commands: &mut Commands, ui: &mut UI) {
+/// Show that the level is complete, with the run's star rating if the level has one.
+pub fn display(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    ui: &mut UI,
+    stars: Option<u8>,
+    lines: &[String],
+) {
     super::display(commands, ui, |parent| {
         let style = TextStyle {
             font: asset_server.load("fonts/DejaVuSansMono.ttf"),
@@ -40,13 +50,36 @@ pub fn display(asset_server: &AssetServer, commands: &mut Commands, ui: &mut UI)
                                 top: Val::Px(200.0),
                                 ..Default::default()
                             },
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
                             ..Default::default()
                         },
                         background_color: Color::NONE.into(),
                         ..Default::default()
                     })
                     .with_children(|parent| {
-                        parent.spawn(TextBundle::from_section("complete!", style));
+                        parent.spawn(TextBundle::from_section("complete!", style.clone()));
+
+                        if let Some(stars) = stars {
+                            parent.spawn(TextBundle::from_section(
+                                format_stars(stars),
+                                TextStyle {
+                                    font_size: 50.0,
+                                    color: Color::GOLD,
+                                    ..style.clone()
+                                },
+                            ));
+                        }
+
+                        for line in lines {
+                            parent.spawn(TextBundle::from_section(
+                                line,
+                                TextStyle {
+                                    font_size: 30.0,
+                                    ..style.clone()
+                                },
+                            ));
+                        }
                     });
 
                 parent
@@ -54,7 +87,7 @@ pub fn display(asset_server: &AssetServer, commands: &mut Commands, ui: &mut UI)
                         style: Style {
                             position_type: PositionType::Absolute,
                             position: UiRect {
-                                top: Val::Px(400.0),
+                                top: Val::Px(500.0),
                                 ..Default::default()
                             },
                             padding: UiRect::all(Val::Px(10.0)),
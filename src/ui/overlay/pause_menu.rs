@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+
+use crate::{
+    settings::Settings,
+    ui::{self, button, UI},
+};
+
+pub enum PauseMenuEvent {
+    Resume,
+    Restart,
+    Settings,
+    /// Go back to the pause menu from the settings.
+    CloseSettings,
+    LevelSelect,
+    QuitToMenu,
+}
+
+fn resume_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::Resume))
+}
+
+fn restart_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::Restart))
+}
+
+fn settings_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::Settings))
+}
+
+fn close_settings_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::CloseSettings))
+}
+
+fn level_select_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::LevelSelect))
+}
+
+fn quit_to_menu_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(PauseMenuEvent::QuitToMenu))
+}
+
+pub fn display(asset_server: &AssetServer, commands: &mut Commands, ui: &mut UI) {
+    super::display(commands, ui, |parent| {
+        let style = TextStyle {
+            font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+            font_size: 40.0,
+            color: Color::WHITE,
+        };
+
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section("paused", style.clone()));
+
+                let buttons: [(&str, button::Callback); 5] = [
+                    ("resume", resume_callback),
+                    ("restart level", restart_callback),
+                    ("settings", settings_callback),
+                    ("level select", level_select_callback),
+                    ("quit to menu", quit_to_menu_callback),
+                ];
+
+                for (label, callback) in buttons {
+                    parent
+                        .spawn(ButtonBundle {
+                            style: Style {
+                                margin: UiRect {
+                                    top: Val::Px(20.0),
+                                    ..Default::default()
+                                },
+                                padding: UiRect::all(Val::Px(10.0)),
+                                ..Default::default()
+                            },
+                            background_color: Color::WHITE.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn(TextBundle::from_section(
+                                label,
+                                TextStyle {
+                                    font_size: 30.0,
+                                    color: Color::BLACK,
+                                    ..style.clone()
+                                },
+                            ));
+                        })
+                        .insert(button::OnClick { callback });
+                }
+            });
+    });
+}
+
+pub fn display_settings(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    ui: &mut UI,
+    settings: &Settings,
+) {
+    super::display(commands, ui, |parent| {
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::NONE.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                ui::settings::spawn(parent, asset_server, settings, close_settings_callback)
+            });
+    });
+}
+
+pub struct PauseMenuPlugin;
+
+impl Plugin for PauseMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PauseMenuEvent>();
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+
+use crate::{
+    player::Player,
+    respawn::{Attempts, Dying},
+};
+
+pub fn create_attempts(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Auto, Val::Px(30.0)),
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    ..default()
+                },
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+                        font_size: 20.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                AttemptsText,
+            ));
+        })
+        .id()
+}
+
+/// A black screen that covers the level while the player respawns.
+pub fn create_fade(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                background_color: Color::NONE.into(),
+                z_index: ZIndex::Global(1),
+                ..default()
+            },
+            Fade,
+        ))
+        .id()
+}
+
+#[derive(Component)]
+struct AttemptsText;
+
+#[derive(Component)]
+struct Fade;
+
+fn update_attempts_text(
+    attempts: Res<Attempts>,
+    added_query: Query<(), Added<AttemptsText>>,
+    mut query: Query<&mut Text, With<AttemptsText>>,
+) {
+    if attempts.is_changed() || !added_query.is_empty() {
+        for mut text in &mut query {
+            text.sections[0].value = format!("attempt {}", attempts.count);
+        }
+    }
+}
+
+fn update_fade(
+    dying_query: Query<&Dying, With<Player>>,
+    mut query: Query<&mut BackgroundColor, With<Fade>>,
+) {
+    let fade = dying_query.iter().next().map_or(0.0, Dying::fade);
+
+    for mut background_color in &mut query {
+        if background_color.0.a() != fade {
+            *background_color = Color::rgba(0.0, 0.0, 0.0, fade).into();
+        }
+    }
+}
+
+pub struct RespawnPlugin;
+
+impl Plugin for RespawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_attempts_text).add_system(update_fade);
+    }
+}
@@ -0,0 +1,275 @@
+use bevy::prelude::*;
+
+use crate::{
+    actions::{InputAction, Rebinding},
+    settings::{Setting, Settings},
+    ui::button,
+};
+
+/// Changes a setting by `steps` steps when it's clicked.
+#[derive(Component)]
+struct SettingButton {
+    setting: Setting,
+    steps: i32,
+}
+
+#[derive(Component)]
+struct SettingValue {
+    setting: Setting,
+}
+
+/// Binds the next button that's pressed to `action` when it's clicked.
+#[derive(Component)]
+struct RebindButton {
+    action: InputAction,
+}
+
+#[derive(Component)]
+struct BindingValue {
+    action: InputAction,
+}
+
+fn binding_text(settings: &Settings, rebinding: Option<&Rebinding>, action: InputAction) -> String {
+    let value = match rebinding {
+        Some(rebinding) if rebinding.action == action => String::from("press a button"),
+        _ => settings.bindings.describe(action),
+    };
+
+    format!("{:>16}", value)
+}
+
+fn spawn_button<T: Component>(
+    parent: &mut ChildBuilder,
+    style: &TextStyle,
+    label: &str,
+    component: T,
+) {
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                padding: UiRect::new(Val::Px(10.0), Val::Px(10.0), Val::Px(2.0), Val::Px(2.0)),
+                margin: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    color: Color::BLACK,
+                    ..style.clone()
+                },
+            ));
+        })
+        .insert(component);
+}
+
+fn spawn_row(parent: &mut ChildBuilder, spawn_children: impl FnOnce(&mut ChildBuilder)) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(spawn_children);
+}
+
+/**
+Add the settings next to the controls, followed by a button that runs `back_callback`.
+
+Each setting has buttons to step it down and up, and each control has a button to rebind it.
+*/
+pub fn spawn(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    settings: &Settings,
+    back_callback: fn(&mut Commands),
+) {
+    let style = TextStyle {
+        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    let column_style = Style {
+        flex_direction: FlexDirection::Column,
+        margin: UiRect::all(Val::Px(10.0)),
+        ..default()
+    };
+
+    parent.spawn(TextBundle {
+        style: Style {
+            margin: UiRect::all(Val::Px(10.0)),
+            ..default()
+        },
+        ..TextBundle::from_section(
+            "Settings",
+            TextStyle {
+                font_size: 40.0,
+                ..style.clone()
+            },
+        )
+    });
+
+    parent.spawn(NodeBundle::default()).with_children(|parent| {
+        parent
+            .spawn(NodeBundle {
+                style: column_style.clone(),
+                ..default()
+            })
+            .with_children(|parent| {
+                for setting in Setting::ALL {
+                    spawn_row(parent, |parent| {
+                        parent.spawn(TextBundle::from_section(
+                            format!("{:<18}", setting.label()),
+                            style.clone(),
+                        ));
+
+                        spawn_button(parent, &style, "-", SettingButton { setting, steps: -1 });
+                        spawn_button(parent, &style, "+", SettingButton { setting, steps: 1 });
+
+                        parent.spawn((
+                            TextBundle::from_section(
+                                format!("{:>10}", settings.describe(setting)),
+                                style.clone(),
+                            ),
+                            SettingValue { setting },
+                        ));
+                    });
+                }
+            });
+
+        parent
+            .spawn(NodeBundle {
+                style: column_style,
+                ..default()
+            })
+            .with_children(|parent| {
+                for action in InputAction::ALL {
+                    spawn_row(parent, |parent| {
+                        parent.spawn(TextBundle::from_section(
+                            format!("{:<15}", action.label()),
+                            style.clone(),
+                        ));
+
+                        parent.spawn((
+                            TextBundle::from_section(
+                                binding_text(settings, None, action),
+                                style.clone(),
+                            ),
+                            BindingValue { action },
+                        ));
+
+                        spawn_button(parent, &style, "rebind", RebindButton { action });
+                    });
+                }
+            });
+    });
+
+    parent
+        .spawn(ButtonBundle {
+            style: Style {
+                padding: UiRect::all(Val::Px(10.0)),
+                margin: UiRect::all(Val::Px(10.0)),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Back",
+                TextStyle {
+                    color: Color::BLACK,
+                    font_size: 30.0,
+                    ..style.clone()
+                },
+            ));
+        })
+        .insert(button::OnClick {
+            callback: back_callback,
+        });
+}
+
+pub fn create(
+    asset_server: &AssetServer,
+    commands: &mut Commands,
+    settings: &Settings,
+    back_callback: fn(&mut Commands),
+) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.4, 0.7, 1.0).into(),
+            ..default()
+        })
+        .with_children(|parent| spawn(parent, asset_server, settings, back_callback))
+        .id()
+}
+
+fn handle_setting_buttons(
+    mut commands: Commands,
+    setting_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    rebind_query: Query<(&Interaction, &RebindButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+) {
+    for (interaction, setting_button) in &setting_query {
+        if let Interaction::Clicked = interaction {
+            settings.adjust(setting_button.setting, setting_button.steps);
+        }
+    }
+
+    for (interaction, rebind_button) in &rebind_query {
+        if let Interaction::Clicked = interaction {
+            commands.insert_resource(Rebinding {
+                action: rebind_button.action,
+            });
+        }
+    }
+}
+
+fn update_setting_values(
+    settings: Res<Settings>,
+    rebinding: Option<Res<Rebinding>>,
+    mut was_rebinding: Local<bool>,
+    mut setting_query: Query<(&mut Text, &SettingValue), Without<BindingValue>>,
+    mut binding_query: Query<(&mut Text, &BindingValue), Without<SettingValue>>,
+) {
+    if settings.is_changed() {
+        for (mut text, setting_value) in &mut setting_query {
+            text.sections[0].value = format!("{:>10}", settings.describe(setting_value.setting));
+        }
+    }
+
+    /* The bindings are refreshed when rebinding starts, stops or moves to another action. */
+    let rebinding_changed = match &rebinding {
+        Some(rebinding) => rebinding.is_changed(),
+        None => *was_rebinding,
+    };
+    *was_rebinding = rebinding.is_some();
+
+    if settings.is_changed() || rebinding_changed {
+        for (mut text, binding_value) in &mut binding_query {
+            text.sections[0].value =
+                binding_text(&settings, rebinding.as_deref(), binding_value.action);
+        }
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems((
+            handle_setting_buttons,
+            update_setting_values.after(handle_setting_buttons),
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    main_menu::MainMenuEvent,
+    reset::ResetCause,
+    stats::{LevelStats, Stats},
+    timer::format_time,
+    ui::button,
+};
+
+/// The name of a level for display, such as `tutorial_1` for `levels/tutorial_1.level.json`.
+pub fn level_name(level_path: &str) -> &str {
+    let file_name = level_path.rsplit('/').next().unwrap_or(level_path);
+    file_name.strip_suffix(".level.json").unwrap_or(file_name)
+}
+
+fn row(name: &str, level_stats: &LevelStats) -> String {
+    format!(
+        "{:<16}{:>9}{:>7}{:>5}{:>7}{:>7}{:>11}{:>6}{:>6}",
+        name,
+        level_stats.attempts,
+        level_stats.resets(ResetCause::Avoid),
+        level_stats.resets(ResetCause::OutOfBounds),
+        level_stats.resets(ResetCause::Manual),
+        level_stats.resets(ResetCause::Retry),
+        format_time(level_stats.seconds_played),
+        format!("{:.1}", level_stats.fuel_collected),
+        level_stats.completions,
+    )
+}
+
+fn back_callback(commands: &mut Commands) {
+    commands.add(|world: &mut World| world.send_event(MainMenuEvent::Back));
+}
+
+pub fn create(asset_server: &AssetServer, commands: &mut Commands, stats: &Stats) -> Entity {
+    let style = TextStyle {
+        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+        font_size: 18.0,
+        color: Color::WHITE,
+    };
+
+    let levels: BTreeMap<_, _> = stats.levels.iter().collect();
+
+    let mut lines = vec![format!(
+        "{:<16}{:>9}{:>7}{:>5}{:>7}{:>7}{:>11}{:>6}{:>6}",
+        "level", "attempts", "avoid", "out", "reset", "retry", "played", "fuel", "done"
+    )];
+    lines.extend(
+        levels
+            .into_iter()
+            .map(|(level_path, level_stats)| row(level_name(level_path), level_stats)),
+    );
+    if lines.len() == 1 {
+        lines.push("no levels played yet".to_string());
+    }
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            background_color: Color::rgb(0.4, 0.7, 1.0).into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                ..TextBundle::from_section(
+                    "Stats",
+                    TextStyle {
+                        font_size: 40.0,
+                        ..style.clone()
+                    },
+                )
+            });
+
+            for line in lines {
+                parent.spawn(TextBundle::from_section(line, style.clone()));
+            }
+
+            parent
+                .spawn(ButtonBundle {
+                    style: Style {
+                        padding: UiRect::all(Val::Px(10.0)),
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        "Back",
+                        TextStyle {
+                            color: Color::BLACK,
+                            font_size: 30.0,
+                            ..style.clone()
+                        },
+                    ));
+                })
+                .insert(button::OnClick {
+                    callback: back_callback,
+                });
+        })
+        .id()
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::{
+    load_level::CurrentLevel,
+    profile::Profile,
+    timer::{format_delta, format_time, LevelTimer},
+};
+
+/// How long a split stays on screen.
+const SPLIT_SECONDS: f32 = 3.0;
+
+pub fn create(commands: &mut Commands, asset_server: &AssetServer) -> Entity {
+    let style = TextStyle {
+        font: asset_server.load("fonts/DejaVuSansMono.ttf"),
+        font_size: 20.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(10.0),
+                    left: Val::Px(320.0),
+                    ..default()
+                },
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Auto, Val::Px(30.0)),
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    background_color: Color::NONE.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle::from_section(format_time(0.0), style.clone()),
+                        TimerText,
+                    ));
+                });
+
+            parent.spawn((
+                TextBundle::from_section("", style),
+                SplitText { hide_at: 0.0 },
+            ));
+        })
+        .id()
+}
+
+#[derive(Component)]
+struct TimerText;
+
+#[derive(Component)]
+struct SplitText {
+    hide_at: f32,
+}
+
+fn update_timer_text(timer: Res<LevelTimer>, mut query: Query<&mut Text, With<TimerText>>) {
+    if timer.is_changed() {
+        for mut text in &mut query {
+            text.sections[0].value = format_time(timer.seconds);
+        }
+    }
+}
+
+fn update_split_text(
+    time: Res<Time>,
+    timer: Res<LevelTimer>,
+    profile: Res<Profile>,
+    current_level: Option<Res<CurrentLevel>>,
+    mut shown_splits: Local<usize>,
+    mut query: Query<(&mut Text, &mut SplitText)>,
+) {
+    let now = time.elapsed_seconds();
+
+    if timer.splits.len() < *shown_splits {
+        *shown_splits = 0;
+    }
+
+    if let Some(split) = timer.splits.get(*shown_splits) {
+        let best = current_level
+            .and_then(|current_level| profile.records.level(&current_level.path))
+            .and_then(|record| record.best_splits.get(*shown_splits));
+
+        let mut value = format!("split {} {}", *shown_splits + 1, format_time(*split));
+        if let Some(best) = best {
+            value.push(' ');
+            value.push_str(&format_delta(*split, *best));
+        }
+
+        for (mut text, mut split_text) in &mut query {
+            text.sections[0].value = value.clone();
+            split_text.hide_at = now + SPLIT_SECONDS;
+        }
+
+        *shown_splits = timer.splits.len();
+    }
+
+    for (mut text, split_text) in &mut query {
+        if now >= split_text.hide_at && !text.sections[0].value.is_empty() {
+            text.sections[0].value.clear();
+        }
+    }
+}
+
+pub struct TimerPlugin;
+
+impl Plugin for TimerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(update_timer_text.run_if(resource_exists::<LevelTimer>()))
+            .add_system(update_split_text.run_if(resource_exists::<LevelTimer>()));
+    }
+}
@@ -9,6 +9,82 @@ pub enum WallType {
     Goal,
 }
 
+impl WallType {
+    pub fn color(&self) -> Color {
+        match self {
+            WallType::Neutral => Color::WHITE,
+            WallType::Avoid => Color::RED,
+            WallType::Goal => Color::GREEN,
+        }
+    }
+}
+
+/// The lowest opacity that a goal or avoid wall can be given.
+pub const MIN_SEMANTIC_ALPHA: f32 = 0.5;
+
+/**
+Overrides for a wall's default material.
+
+Goal and avoid walls keep their colour so that the player can always tell what they do: `base_color`
+only applies to neutral walls, and `alpha` can't make a goal or avoid wall more transparent than
+[`MIN_SEMANTIC_ALPHA`].
+*/
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub struct Appearance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_color: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emissive: Option<Color>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub perceptual_roughness: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metallic: Option<f32>,
+    /// `1.0` is opaque and `0.0` is invisible.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alpha: Option<f32>,
+}
+
+pub fn material(wall_type: &WallType, appearance: Option<&Appearance>) -> StandardMaterial {
+    let default_material = StandardMaterial::from(wall_type.color());
+
+    let appearance = match appearance {
+        Some(appearance) => appearance,
+        None => return default_material,
+    };
+
+    let mut base_color = match (wall_type, appearance.base_color) {
+        (WallType::Neutral, Some(base_color)) => base_color,
+        (_, Some(_)) => {
+            warn!("ignoring base_color of {:?} wall", wall_type);
+            wall_type.color()
+        }
+        (_, None) => wall_type.color(),
+    };
+
+    let alpha = appearance.alpha.unwrap_or(base_color.a());
+    let alpha = match wall_type {
+        WallType::Neutral => alpha,
+        WallType::Avoid | WallType::Goal => alpha.max(MIN_SEMANTIC_ALPHA),
+    }
+    .clamp(0.0, 1.0);
+    base_color.set_a(alpha);
+
+    StandardMaterial {
+        base_color,
+        emissive: appearance.emissive.unwrap_or(default_material.emissive),
+        perceptual_roughness: appearance
+            .perceptual_roughness
+            .unwrap_or(default_material.perceptual_roughness),
+        metallic: appearance.metallic.unwrap_or(default_material.metallic),
+        alpha_mode: if alpha < 1.0 {
+            AlphaMode::Blend
+        } else {
+            AlphaMode::Opaque
+        },
+        ..default_material
+    }
+}
+
 #[derive(Component)]
 pub struct Wall {
     pub wall_type: WallType,
@@ -30,7 +106,7 @@ impl WallBundle {
         wall_type: WallType,
         transform: Transform,
         size: Vec2,
-        color: Color,
+        material: StandardMaterial,
     ) -> Self {
         let width = size.x;
         let height = 0.1;
@@ -39,7 +115,7 @@ impl WallBundle {
         Self {
             pbr_bundle: PbrBundle {
                 mesh: meshes.add(Mesh::from(shape::Box::new(width, height, depth))),
-                material: materials.add(color.into()),
+                material: materials.add(material),
                 transform,
                 ..default()
             },
@@ -55,6 +131,7 @@ impl WallBundle {
         position: Vec3,
         rotation: Quat,
         size: Vec2,
+        appearance: Option<&Appearance>,
     ) -> Self {
         WallBundle::new(
             meshes,
@@ -64,7 +141,7 @@ impl WallBundle {
                 .with_translation(position)
                 .with_rotation(rotation),
             size,
-            Color::GREEN,
+            material(&WallType::Goal, appearance),
         )
     }
 
@@ -74,6 +151,7 @@ impl WallBundle {
         position: Vec3,
         rotation: Quat,
         size: Vec2,
+        appearance: Option<&Appearance>,
     ) -> Self {
         WallBundle::new(
             meshes,
@@ -83,7 +161,7 @@ impl WallBundle {
                 .with_translation(position)
                 .with_rotation(rotation),
             size,
-            Color::RED,
+            material(&WallType::Avoid, appearance),
         )
     }
 
@@ -93,6 +171,7 @@ impl WallBundle {
         position: Vec3,
         rotation: Quat,
         size: Vec2,
+        appearance: Option<&Appearance>,
     ) -> Self {
         WallBundle::new(
             meshes,
@@ -102,7 +181,7 @@ impl WallBundle {
                 .with_translation(position)
                 .with_rotation(rotation),
             size,
-            Color::WHITE,
+            material(&WallType::Neutral, appearance),
         )
     }
 }